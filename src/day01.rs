@@ -1,47 +1,208 @@
-use hashbrown::HashMap;
-
-type Digits = HashMap<String, u32>;
-
-fn calibration_value(line: &str, digits: &Digits) -> u32 {
-    let first_digit = digits
-        .iter()
-        .map(|(digit_string, &value)| (line.find(digit_string), value))
-        .filter(|(index, _)| index.is_some())
-        .min()
-        .unwrap()
-        .1;
-    let last_digit = digits
-        .iter()
-        .map(|(digit_string, &value)| (line.rfind(digit_string), value))
-        .max()
-        .unwrap()
-        .1;
-    first_digit * 10 + last_digit
+use crate::utils::aho_corasick::AhoCorasick;
+use crate::utils::parse::ParseError;
+
+use std::io;
+use std::path::Path;
+
+/// The words standing for digits, with their values, e.g. ("7", 7) or ("seven", 7)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitVocabulary {
+    words: Vec<(String, u32)>,
 }
 
-pub fn run(input: &str) {
-    // digits 0 to 9
-    let digits: HashMap<String, u32> = (0..=9).map(|i| (i.to_string(), i)).collect();
+impl DigitVocabulary {
+    pub const PRESETS: [&str; 6] = ["digits", "english", "french", "german", "roman", "ordinals"];
 
-    let sum_of_calibration_values: u32 = input
-        .lines()
-        .map(|line| calibration_value(line, &digits))
-        .sum();
+    /// Only the digits 0 to 9, as in part 1
+    pub fn digits() -> Self {
+        Self {
+            words: (0..=9).map(|value| (value.to_string(), value)).collect(),
+        }
+    }
+
+    /// The digits, plus words for 1 to 9
+    fn with_names(names: [&str; 9]) -> Self {
+        let mut vocabulary = Self::digits();
+        for (name, value) in names.into_iter().zip(1..) {
+            vocabulary = vocabulary.with_word(name, value);
+        }
+        vocabulary
+    }
+
+    /// The digits and the English words, as in part 2
+    pub fn english() -> Self {
+        Self::with_names([
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ])
+    }
+
+    pub fn french() -> Self {
+        Self::with_names([
+            "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+        ])
+    }
 
-    println!("{sum_of_calibration_values}");
+    pub fn german() -> Self {
+        Self::with_names([
+            "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+        ])
+    }
 
-    // digits 0 to 9 plus one, two, ..., nine
-    let digits: HashMap<String, u32> = "one,two,three,four,five,six,seven,eight,nine"
-        .split(',')
-        .map(|s| s.to_string())
-        .zip(1..=9)
-        .chain(digits)
-        .collect();
+    /// The digits and the Roman numerals from I to IX
+    pub fn roman() -> Self {
+        Self::with_names(["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"])
+    }
+
+    /// The digits and the English ordinals, from "first" to "ninth"
+    pub fn ordinals() -> Self {
+        Self::with_names([
+            "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+        ])
+    }
+
+    /// One of the PRESETS
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "digits" => Some(Self::digits()),
+            "english" => Some(Self::english()),
+            "french" => Some(Self::french()),
+            "german" => Some(Self::german()),
+            "roman" => Some(Self::roman()),
+            "ordinals" => Some(Self::ordinals()),
+            _ => None,
+        }
+    }
+
+    /// Adds a custom word, or changes the value of an existing one
+    pub fn with_word(mut self, word: &str, value: u32) -> Self {
+        assert!(!word.is_empty() && value <= 9, "Invalid digit word");
+        match self.words.iter_mut().find(|(existing, _)| existing == word) {
+            Some((_, existing_value)) => *existing_value = value,
+            None => self.words.push((word.to_string(), value)),
+        }
+        self
+    }
+
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words
+    }
+
+    /// Parses a vocabulary with a "word value" pair per line, e.g. "sieben 7". Empty lines and
+    /// lines starting with '#' are ignored.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut words: Vec<(String, u32)> = vec![];
+        let mut position = 0;
+        for line in text.split_inclusive('\n') {
+            let content = line.trim();
+            let error = |expected: &str| ParseError {
+                position,
+                expected: expected.to_string(),
+            };
+            if !content.is_empty() && !content.starts_with('#') {
+                let (word, value) = content
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| error("a word and its value"))?;
+                let value = value
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|&value| value <= 9)
+                    .ok_or_else(|| error("a digit value (0 to 9)"))?;
+                if words.iter().any(|(existing, _)| existing == word) {
+                    return Err(error("a word defined only once"));
+                }
+                words.push((word.to_string(), value));
+            }
+            position += line.len();
+        }
+        if words.is_empty() {
+            return Err(ParseError {
+                position,
+                expected: "at least one word".to_string(),
+            });
+        }
+        Ok(Self { words })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
 
-    let sum_of_calibration_values: u32 = input
+/// Finds the digits of a line, written as digits or words, with two automata built once from
+/// the dictionary: one scanning forward, and one scanning backward with the reversed words
+pub struct DigitScanner {
+    forward: AhoCorasick,
+    backward: AhoCorasick,
+    values: Vec<u32>,
+}
+
+impl DigitScanner {
+    pub fn new(vocabulary: &DigitVocabulary) -> Self {
+        let digits = vocabulary.words();
+        let words: Vec<_> = digits.iter().map(|(word, _)| word.as_bytes()).collect();
+        let reversed_words: Vec<Vec<u8>> = words
+            .iter()
+            .map(|word| word.iter().rev().copied().collect())
+            .collect();
+        Self {
+            forward: AhoCorasick::new(&words),
+            backward: AhoCorasick::new(&reversed_words),
+            values: digits.iter().map(|&(_, value)| value).collect(),
+        }
+    }
+
+    /// The digit starting first (the longest word if several start there)
+    pub fn first_digit(&self, line: &str) -> Option<u32> {
+        let occurrence = self.forward.find_leftmost(line.bytes())?;
+        Some(self.values[occurrence.pattern])
+    }
+
+    /// The digit ending last (the longest word if several end there, so that "VIII" wins over
+    /// the "I" at its end): the leftmost reversed word when scanning backward
+    pub fn last_digit(&self, line: &str) -> Option<u32> {
+        let occurrence = self.backward.find_leftmost(line.bytes().rev())?;
+        Some(self.values[occurrence.pattern])
+    }
+
+    /// All the digits of the line, by start position, overlapping ones included (so "oneight"
+    /// is 1 then 8). Of several words starting at the same position, only the longest counts.
+    pub fn digits(&self, line: &str) -> Vec<u32> {
+        let mut occurrences: Vec<_> = self.forward.find_overlapping(line.bytes()).collect();
+        occurrences.sort_by_key(|occurrence| (occurrence.start, std::cmp::Reverse(occurrence.end)));
+        occurrences.dedup_by_key(|occurrence| occurrence.start);
+        occurrences
+            .iter()
+            .map(|occurrence| self.values[occurrence.pattern])
+            .collect()
+    }
+}
+
+fn calibration_value(line: &str, scanner: &DigitScanner) -> u32 {
+    let first_digit = scanner.first_digit(line).expect("No digit in line");
+    let last_digit = scanner.last_digit(line).unwrap();
+    first_digit * 10 + last_digit
+}
+
+fn sum_of_calibration_values(input: &str, vocabulary: &DigitVocabulary) -> u32 {
+    let scanner = DigitScanner::new(vocabulary);
+    input
         .lines()
-        .map(|line| calibration_value(line, &digits))
-        .sum();
+        .map(|line| calibration_value(line, &scanner))
+        .sum()
+}
+
+/// Solves part 1 with the digits only, and part 2 with the vocabulary
+pub fn solve_with_vocabulary(input: &str, vocabulary: &DigitVocabulary) -> Vec<String> {
+    let mut answers = vec![];
+    for vocabulary in [&DigitVocabulary::digits(), vocabulary] {
+        let sum_of_calibration_values = sum_of_calibration_values(input, vocabulary);
+        answers.push(sum_of_calibration_values.to_string());
+    }
+    answers
+}
 
-    println!("{sum_of_calibration_values}");
+pub fn solve(input: &str) -> Vec<String> {
+    solve_with_vocabulary(input, &DigitVocabulary::english())
 }
@@ -1,51 +1,292 @@
+use crate::utils::linalg::Rational;
+use crate::utils::parse::{
+    delimited, identifier, integer, literal, map, pair, parse_str, separated, terminated,
+};
 use itertools::Itertools;
+use num::{BigInt, One, ToPrimitive, Zero};
+use std::collections::BTreeMap;
+use std::fmt;
 
-/// Parses a line and returns the game id and the max number of balls per color
-fn parse_line(line: &str) -> (u32, [u32; 3]) {
-    let mut parts = line.split(':');
-    let header = parts.next().expect("Invalid line format (header)");
-    let game_id: u32 = header["Game ".len()..]
-        .parse()
-        .expect("Invalid line format (game id)");
-    let sets = parts.next().expect("Invalid line format (sets)");
-    let mut maxs: [u32; 3] = [0, 0, 0];
-    for n_color in sets.split(|c| c == ';' || c == ',') {
-        let mut set_parts = n_color[1..].split_whitespace();
-        let number: u32 = set_parts
-            .next()
-            .expect("Invalid line format (no number)")
-            .parse()
-            .expect("Invalid line format (number format)");
-        let color = set_parts.next().expect("Invalid line format (no color)");
-        let index = match color {
-            "red" => 0,
-            "green" => 1,
-            "blue" => 2,
-            _ => unreachable!(),
-        };
-        maxs[index] = maxs[index].max(number);
-    }
-    (game_id, maxs)
-}
-
-fn is_possible(maxs: &[u32; 3]) -> bool {
-    maxs[0] <= 12 && maxs[1] <= 13 && maxs[2] <= 14
-}
-
-fn power(maxs: &[u32; 3]) -> u32 {
-    maxs[0] * maxs[1] * maxs[2]
-}
-
-pub fn run(input: &str) {
+/// Cubes of one color shown by the elf, e.g. "3 blue"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub count: u32,
+    pub color: String,
+}
+
+/// A game, with every set of cubes shown by the elf, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Vec<Draw>>,
+}
+
+/// The number of cubes of each color in a bag (0 for the colors it doesn't mention)
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bag {
+    counts: BTreeMap<String, u32>,
+}
+
+/// Statistics over every draw of a color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorStats {
+    pub num_draws: usize,
+    pub total: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl ColorStats {
+    pub fn mean(&self) -> f64 {
+        self.total as f64 / self.num_draws as f64
+    }
+}
+
+/// Parses a line, keeping every draw of every set
+pub fn parse_line(line: &str) -> Game {
+    let draw = map(
+        pair(terminated(integer(), literal(" ")), identifier()),
+        |(count, color)| Draw {
+            count,
+            color: color.to_string(),
+        },
+    );
+    let game = pair(
+        delimited(literal("Game "), integer(), literal(": ")),
+        separated(separated(draw, literal(", ")), literal("; ")),
+    );
+    let (id, sets) = parse_str(game, line).expect("Invalid line format");
+    Game { id, sets }
+}
+
+impl Game {
+    pub fn draws(&self) -> impl Iterator<Item = &Draw> {
+        self.sets.iter().flatten()
+    }
+
+    /// The cubes of every set (if a color is drawn several times in a set, the counts add up)
+    pub fn set_bags(&self) -> impl Iterator<Item = Bag> + '_ {
+        self.sets.iter().map(|set| {
+            let mut bag = Bag::default();
+            for draw in set {
+                *bag.counts.entry(draw.color.clone()).or_default() += draw.count;
+            }
+            bag
+        })
+    }
+
+    /// The fewest cubes of each color that make the game possible
+    pub fn minimum_bag(&self) -> Bag {
+        self.set_bags()
+            .fold(Bag::default(), |bag, set_bag| bag.merge(&set_bag))
+    }
+
+    /// Whether every set of the game could have been drawn from the bag
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        self.set_bags().all(|set_bag| set_bag.fits_in(bag))
+    }
+}
+
+impl Bag {
+    pub fn new<'a>(counts: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Self {
+            counts: counts
+                .into_iter()
+                .map(|(color, count)| (color.to_string(), count))
+                .collect(),
+        }
+    }
+
+    pub fn count(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(|color| color.as_str())
+    }
+
+    pub fn num_cubes(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// The product of the numbers of cubes of the colors
+    pub fn power(&self, colors: &[&str]) -> u32 {
+        colors.iter().map(|color| self.count(color)).product()
+    }
+
+    /// Whether all these cubes could be taken from the other bag at once
+    pub fn fits_in(&self, other: &Bag) -> bool {
+        self.counts
+            .iter()
+            .all(|(color, &count)| count <= other.count(color))
+    }
+
+    /// The smallest bag containing both bags
+    pub fn merge(&self, other: &Bag) -> Bag {
+        let mut merged = self.clone();
+        for (color, &count) in &other.counts {
+            let merged_count = merged.counts.entry(color.clone()).or_default();
+            *merged_count = (*merged_count).max(count);
+        }
+        merged
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = self
+            .counts
+            .iter()
+            .map(|(color, count)| format!("{count} {color}"))
+            .join(", ");
+        write!(f, "{counts}")
+    }
+}
+
+/// The games that could have been played with the bag
+pub fn possible_games<'a>(games: &'a [Game], bag: &Bag) -> Vec<&'a Game> {
+    games
+        .iter()
+        .filter(|game| game.is_possible_with(bag))
+        .collect()
+}
+
+/// The smallest bag with which all the games could have been played
+pub fn minimum_bag<'a>(games: impl IntoIterator<Item = &'a Game>) -> Bag {
+    games
+        .into_iter()
+        .fold(Bag::default(), |bag, game| bag.merge(&game.minimum_bag()))
+}
+
+/// Statistics of every color over all the draws of the games
+pub fn color_stats(games: &[Game]) -> BTreeMap<String, ColorStats> {
+    let mut stats = BTreeMap::new();
+    for draw in games.iter().flat_map(Game::draws) {
+        stats
+            .entry(draw.color.clone())
+            .and_modify(|stats: &mut ColorStats| {
+                stats.num_draws += 1;
+                stats.total += draw.count;
+                stats.min = stats.min.min(draw.count);
+                stats.max = stats.max.max(draw.count);
+            })
+            .or_insert(ColorStats {
+                num_draws: 1,
+                total: draw.count,
+                min: draw.count,
+                max: draw.count,
+            });
+    }
+    stats
+}
+
+/// The number of ways to choose k among n
+fn choose(n: u32, k: u32) -> BigInt {
+    if k > n {
+        return BigInt::zero();
+    }
+    // every intermediate value is a binomial coefficient, so the divisions are exact
+    (0..k).fold(BigInt::one(), |ways, i| ways * (n - i) / (i + 1))
+}
+
+/// The probability to draw exactly these cubes at once from the bag, without replacement
+/// (multivariate hypergeometric distribution)
+fn set_likelihood(set_bag: &Bag, bag: &Bag) -> Rational {
+    let num_ways: BigInt = set_bag
+        .counts
+        .iter()
+        .map(|(color, &count)| choose(bag.count(color), count))
+        .product();
+    let num_draws = choose(bag.num_cubes(), set_bag.num_cubes());
+    Rational::new(num_ways, num_draws)
+}
+
+/// The probability of the game, the cubes being put back in the bag after each set. It is
+/// exact, so that bags equally likely are tied.
+fn game_likelihood(game: &Game, bag: &Bag) -> Rational {
+    game.set_bags()
+        .map(|set_bag| set_likelihood(&set_bag, bag))
+        .product()
+}
+
+/// The probability to observe exactly the sets of the game with the bag
+pub fn game_probability(game: &Game, bag: &Bag) -> f64 {
+    game_likelihood(game, bag).to_f64().unwrap()
+}
+
+/// The most plausible bags for the game, with their probabilities, among the bags of at most
+/// max_cubes cubes (the likelihood can keep growing with the size of the bag). Only the colors
+/// of the game are considered, since any other color could only make the draws less likely.
+/// Ties go to the bags with fewer cubes, then with fewer cubes of the first colors in
+/// alphabetical order.
+pub fn rank_bags(game: &Game, max_cubes: u32, num_bags: usize) -> Vec<(Bag, f64)> {
+    let minimum_bag = game.minimum_bag();
+    let colors: Vec<_> = minimum_bag.colors().collect();
+    // no color can have more extra cubes than that
+    let max_extra_cubes = max_cubes.saturating_sub(minimum_bag.num_cubes());
+    let mut bags: Vec<_> = colors
+        .iter()
+        .map(|color| {
+            let min_count = minimum_bag.count(color);
+            min_count..=min_count + max_extra_cubes
+        })
+        .multi_cartesian_product()
+        .filter(|counts| counts.iter().sum::<u32>() <= max_cubes)
+        .map(|counts| {
+            let bag = Bag::new(colors.iter().copied().zip(counts));
+            let likelihood = game_likelihood(game, &bag);
+            (bag, likelihood)
+        })
+        .collect();
+    bags.sort_by(|(bag1, likelihood1), (bag2, likelihood2)| {
+        likelihood2
+            .cmp(likelihood1)
+            .then(bag1.num_cubes().cmp(&bag2.num_cubes()))
+            .then(bag1.cmp(bag2))
+    });
+    bags.into_iter()
+        .take(num_bags)
+        .map(|(bag, likelihood)| (bag, likelihood.to_f64().unwrap()))
+        .collect()
+}
+
+/// The maximum-likelihood estimate of the bag, among the bags of at most max_cubes cubes
+pub fn estimate_bag(game: &Game, max_cubes: u32) -> Option<(Bag, f64)> {
+    rank_bags(game, max_cubes, 1).pop()
+}
+
+/// The most plausible bags of every game, with at most a few more cubes than its minimum bag
+pub fn trace(input: &str) -> String {
+    const MAX_EXTRA_CUBES: u32 = 10;
+    input
+        .lines()
+        .map(|line| {
+            let game = parse_line(line);
+            let max_cubes = game.minimum_bag().num_cubes() + MAX_EXTRA_CUBES;
+            let bags = rank_bags(&game, max_cubes, 3)
+                .iter()
+                .map(|(bag, probability)| format!("{bag} ({probability:.3e})"))
+                .join("; ");
+            format!("Game {}: {bags}\n", game.id)
+        })
+        .collect()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let all_games = input.lines().map(parse_line).collect_vec();
 
-    let sum_of_possible_game_ids: u32 = all_games
+    let bag = Bag::new([("red", 12), ("green", 13), ("blue", 14)]);
+    let sum_of_possible_game_ids: u32 = possible_games(&all_games, &bag)
         .iter()
-        .filter(|(_, maxs)| is_possible(maxs))
-        .map(|(game_id, _)| game_id)
+        .map(|game| game.id)
         .sum();
-    println!("{}", sum_of_possible_game_ids);
+    answers.push(sum_of_possible_game_ids.to_string());
 
-    let sum_of_game_powers: u32 = all_games.iter().map(|(_, maxs)| power(maxs)).sum();
-    println!("{}", sum_of_game_powers);
+    let sum_of_game_powers: u32 = all_games
+        .iter()
+        .map(|game| game.minimum_bag().power(&["red", "green", "blue"]))
+        .sum();
+    answers.push(sum_of_game_powers.to_string());
+    answers
 }
@@ -1,102 +1,148 @@
-#[derive(Debug)]
-struct PartNumber {
-    number: i32,
-    x1: i32,
-    x2: i32,
-    y: i32,
-}
+use crate::utils::Grid;
 
-#[derive(Debug)]
-struct Symbol {
-    x: i32,
-    y: i32,
-    is_star: bool,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub number: u32,
+    pub x1: usize,
+    pub x2: usize,
+    pub y: usize,
 }
 
-impl PartNumber {
-    fn is_adjacent_to_symbol(&self, symbol: &Symbol) -> bool {
-        (self.x1 - 1..=self.x2 + 1).contains(&symbol.x)
-            && (self.y - 1..=self.y + 1).contains(&symbol.y)
-    }
-
-    fn is_adjacent_to_any_symbol(&self, symbols: &[Symbol]) -> bool {
-        symbols
-            .iter()
-            .any(|symbol| self.is_adjacent_to_symbol(symbol))
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub x: usize,
+    pub y: usize,
+    pub symbol: u8,
 }
 
-impl Symbol {
-    fn gear_ratio(&self, part_numbers: &[PartNumber]) -> i32 {
-        if !self.is_star {
-            return 0;
-        }
-        let mut ratio = 1;
-        let mut number_of_adjacent_parts = 0;
-        for pn in part_numbers {
-            if pn.is_adjacent_to_symbol(self) {
-                number_of_adjacent_parts += 1;
-                if number_of_adjacent_parts > 2 {
-                    return 0;
-                }
-                ratio *= pn.number;
-            }
-        }
-        if number_of_adjacent_parts != 2 {
-            return 0;
-        }
-        ratio
-    }
+/// The numbers and symbols of an engine schematic, with a grid giving the number or symbol
+/// (as an index) covering each cell, so that neighborhoods are looked up instead of scanned
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    part_numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+    number_at: Grid<Option<usize>>,
+    symbol_at: Grid<Option<usize>>,
 }
 
-fn parse_input(input: &str) -> (Vec<PartNumber>, Vec<Symbol>) {
-    let mut part_numbers: Vec<PartNumber> = vec![];
-    let mut symbols: Vec<Symbol> = vec![];
-    for (y, line) in input.lines().enumerate() {
-        let mut n: Option<i32> = None;
-        let mut length: i32 = 0;
-        for (x, &c) in line.as_bytes().iter().chain([b'.'].iter()).enumerate() {
-            if c.is_ascii_digit() {
-                length += 1;
-                let digit = (c - b'0') as i32;
-                n = n.map_or(Some(digit), |number| Some(number * 10 + digit));
-            } else {
-                if let Some(number) = n {
-                    part_numbers.push(PartNumber {
+impl Schematic {
+    pub fn parse(input: &str) -> Self {
+        let lines: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let mut schematic = Self {
+            part_numbers: vec![],
+            symbols: vec![],
+            number_at: Grid::from_fn(width, lines.len(), |_, _| None),
+            symbol_at: Grid::from_fn(width, lines.len(), |_, _| None),
+        };
+        for (y, line) in lines.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                let c = line[x];
+                if c.is_ascii_digit() {
+                    let x1 = x;
+                    let mut number = 0;
+                    while x < line.len() && line[x].is_ascii_digit() {
+                        number = number * 10 + (line[x] - b'0') as u32;
+                        schematic
+                            .number_at
+                            .set(x, y, Some(schematic.part_numbers.len()));
+                        x += 1;
+                    }
+                    schematic.part_numbers.push(PartNumber {
                         number,
-                        x1: x as i32 - length,
-                        x2: x as i32 - 1,
-                        y: y as i32,
+                        x1,
+                        x2: x - 1,
+                        y,
                     });
+                    continue;
                 }
-                n = None;
-                length = 0;
                 if c != b'.' {
-                    symbols.push(Symbol {
-                        x: x as i32,
-                        y: y as i32,
-                        is_star: c == b'*',
-                    })
+                    schematic.symbol_at.set(x, y, Some(schematic.symbols.len()));
+                    schematic.symbols.push(Symbol { x, y, symbol: c });
                 }
+                x += 1;
             }
         }
+        schematic
+    }
+
+    pub fn part_numbers(&self) -> &[PartNumber] {
+        &self.part_numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The cells around the rectangle from (x1, y1) to (x2, y2) and in it, inside the grid
+    fn cells_around(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> Vec<(usize, usize)> {
+        let xs = x1.saturating_sub(1)..=(x2 + 1).min(self.number_at.width().saturating_sub(1));
+        let ys = y1.saturating_sub(1)..=(y2 + 1).min(self.number_at.height().saturating_sub(1));
+        ys.flat_map(|y| xs.clone().map(move |x| (x, y))).collect()
+    }
+
+    /// The numbers with a digit next to the cell (diagonally included), each only once
+    pub fn numbers_adjacent_to(&self, x: usize, y: usize) -> Vec<&PartNumber> {
+        let mut indices: Vec<_> = self
+            .cells_around(x, y, x, y)
+            .into_iter()
+            .filter_map(|(x, y)| *self.number_at.get(x, y))
+            .collect();
+        indices.sort();
+        indices.dedup();
+        indices
+            .into_iter()
+            .map(|index| &self.part_numbers[index])
+            .collect()
+    }
+
+    /// The symbols next to a digit of the number (diagonally included)
+    pub fn symbols_adjacent_to(&self, part_number: &PartNumber) -> Vec<&Symbol> {
+        let (x1, x2, y) = (part_number.x1, part_number.x2, part_number.y);
+        self.cells_around(x1, y, x2, y)
+            .into_iter()
+            .filter_map(|(x, y)| *self.symbol_at.get(x, y))
+            .map(|index| &self.symbols[index])
+            .collect()
+    }
+
+    /// The numbers next to at least one symbol
+    pub fn valid_part_numbers(&self) -> impl Iterator<Item = &PartNumber> {
+        self.part_numbers
+            .iter()
+            .filter(|part_number| !self.symbols_adjacent_to(part_number).is_empty())
+    }
+
+    /// The symbols of a kind that are next to exactly arity numbers, with these numbers (the
+    /// gears are the '*' of arity 2)
+    pub fn symbols_with_arity(
+        &self,
+        symbol: u8,
+        arity: usize,
+    ) -> impl Iterator<Item = (&Symbol, Vec<&PartNumber>)> {
+        self.symbols
+            .iter()
+            .filter(move |candidate| candidate.symbol == symbol)
+            .map(|symbol| (symbol, self.numbers_adjacent_to(symbol.x, symbol.y)))
+            .filter(move |(_, part_numbers)| part_numbers.len() == arity)
     }
-    (part_numbers, symbols)
 }
 
-pub fn run(input: &str) {
-    let (part_numbers, symbols) = parse_input(input);
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let schematic = Schematic::parse(input);
 
-    let sum_of_valid_part_numbers: i32 = part_numbers
-        .iter()
-        .filter(|pn| pn.is_adjacent_to_any_symbol(&symbols))
-        .map(|pn| pn.number)
+    let sum_of_valid_part_numbers: u32 = schematic
+        .valid_part_numbers()
+        .map(|part_number| part_number.number)
         .sum();
-    println!("{}", sum_of_valid_part_numbers);
+    answers.push(sum_of_valid_part_numbers.to_string());
 
-    let sum_of_gear_ratios: i32 = symbols
-        .iter()
-        .map(|symbol| symbol.gear_ratio(&part_numbers))
+    let sum_of_gear_ratios: u32 = schematic
+        .symbols_with_arity(b'*', 2)
+        .map(|(_, part_numbers)| part_numbers[0].number * part_numbers[1].number)
         .sum();
-    println!("{}", sum_of_gear_ratios);
+    answers.push(sum_of_gear_ratios.to_string());
+    answers
 }
@@ -1,40 +1,139 @@
 use crate::utils::parse_ints;
+use num::bigint::BigInt;
+use num::{One, Zero};
+use std::fmt;
+use std::ops::AddAssign;
 
+/// A set of numbers, as a bitset for the numbers under 128 (all of them in the real cards)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct NumberSet {
+    small: u128,
+    large: Vec<u32>,
+}
+
+impl NumberSet {
+    fn new(numbers: &[u32]) -> Self {
+        let mut set = Self::default();
+        for &number in numbers {
+            if number < 128 {
+                set.small |= 1 << number;
+            } else if !set.large.contains(&number) {
+                set.large.push(number);
+            }
+        }
+        set
+    }
+
+    fn contains(&self, number: u32) -> bool {
+        if number < 128 {
+            self.small & (1 << number) != 0
+        } else {
+            self.large.contains(&number)
+        }
+    }
+}
+
+/// How many of our numbers are winning numbers: a number we have twice counts twice, but a
+/// winning number listed twice doesn't
 fn n_matching(winning_numbers: &[u32], our_numbers: &[u32]) -> usize {
-    winning_numbers
+    let winning_numbers = NumberSet::new(winning_numbers);
+    our_numbers
         .iter()
-        .filter(|w| our_numbers.contains(w))
+        .filter(|&&number| winning_numbers.contains(number))
         .count()
 }
 
-pub fn run(input: &str) {
-    let scratch_cards: Vec<_> = input
+/// The number of instances of every card, originals and copies, given the number of matching
+/// numbers of every card. The counts grow exponentially, so T can be u128 or BigInt.
+pub fn count_cards<T>(n_matching: &[usize]) -> Vec<T>
+where
+    T: Clone + One + for<'a> AddAssign<&'a T>,
+{
+    let num_cards = n_matching.len();
+    let mut instances = vec![T::one(); num_cards];
+    for (i, &matching) in n_matching.iter().enumerate() {
+        let copies = instances[i].clone();
+        for card_instances in &mut instances[i + 1..num_cards.min(i + 1 + matching)] {
+            *card_instances += &copies;
+        }
+    }
+    instances
+}
+
+/// Where the copies of every card come from: each instance of a card wins one copy of each of
+/// the next cards, as many as its matching numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyTrace<T> {
+    /// The number of instances of every card, the original included
+    pub instances: Vec<T>,
+    /// For every card, the earlier cards that won copies of it, with the number of copies
+    pub sources: Vec<Vec<(usize, T)>>,
+}
+
+pub fn trace_copies<T>(n_matching: &[usize]) -> CopyTrace<T>
+where
+    T: Clone + One + for<'a> AddAssign<&'a T>,
+{
+    let num_cards = n_matching.len();
+    let mut trace = CopyTrace {
+        instances: vec![T::one(); num_cards],
+        sources: vec![vec![]; num_cards],
+    };
+    for (i, &matching) in n_matching.iter().enumerate() {
+        let final_index = num_cards.min(i + 1 + matching);
+        let copies = trace.instances[i].clone();
+        for j in i + 1..final_index {
+            trace.instances[j] += &copies;
+            trace.sources[j].push((i, copies.clone()));
+        }
+    }
+    trace
+}
+
+impl<T: fmt::Display> fmt::Display for CopyTrace<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, (instances, sources)) in self.instances.iter().zip(&self.sources).enumerate() {
+            write!(f, "Card {}: {instances} instances (1 original", index + 1)?;
+            for (source, copies) in sources {
+                write!(f, ", {copies} from card {}", source + 1)?;
+            }
+            writeln!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_matching_numbers(input: &str) -> Vec<usize> {
+    input
         .lines()
         .map(|line| {
             let mut parts = line.split(": ").nth(1).unwrap().split(" | ");
             let winning_numbers: Vec<u32> = parse_ints(parts.next().unwrap(), false);
             let our_numbers: Vec<u32> = parse_ints(parts.next().unwrap(), false);
-            (winning_numbers, our_numbers)
+            n_matching(&winning_numbers, &our_numbers)
         })
-        .collect();
+        .collect()
+}
 
-    let total_points: u32 = scratch_cards
+/// The trace of the copies won by the cards
+pub fn trace(input: &str) -> CopyTrace<BigInt> {
+    trace_copies(&parse_matching_numbers(input))
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let n_matching = parse_matching_numbers(input);
+
+    let total_points: BigInt = n_matching
         .iter()
-        .map(|(winning_numbers, our_numbers)| {
-            u32::pow(2, n_matching(winning_numbers, our_numbers) as u32) / 2
-        })
+        .filter(|&&matching| matching > 0)
+        .map(|&matching| BigInt::one() << (matching - 1))
         .sum();
-    println!("{}", total_points);
+    answers.push(total_points.to_string());
 
-    let mut n_cards = vec![1; scratch_cards.len()];
-    for (i, (winning_numbers, our_numbers)) in scratch_cards.iter().enumerate() {
-        let final_index = scratch_cards
-            .len()
-            .min(i + 1 + n_matching(winning_numbers, our_numbers));
-        for j in i + 1..final_index {
-            n_cards[j] += n_cards[i]
-        }
-    }
-    let total_n_cards: u32 = n_cards.iter().sum();
-    println!("{}", total_n_cards);
+    let total_n_cards = count_cards::<BigInt>(&n_matching)
+        .iter()
+        .fold(BigInt::zero(), |total, instances| total + instances);
+    answers.push(total_n_cards.to_string());
+    answers
 }
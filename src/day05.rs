@@ -1,5 +1,9 @@
-use crate::utils::parse_ints;
+use crate::utils::parse::{
+    blocks, identifier, integer, literal, map, optional, pair, parse_all, preceded, separated,
+    terminated,
+};
 use itertools::Itertools;
+use std::fmt;
 use std::ops::Range;
 
 #[derive(Debug)]
@@ -8,97 +12,287 @@ struct ElfMap {
     offset: i64,
 }
 
-fn get_seed_destination(maps: &[ElfMap], source: i64) -> i64 {
-    // For part 1, each number gets mapped to a single number
-    for map in maps {
-        if map.source_range.contains(&source) {
-            return source + map.offset;
+/// Two maps of a category sending the same values to different places
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapError {
+    pub first: Range<i64>,
+    pub second: Range<i64>,
+}
+
+impl fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (first, second) = (&self.first, &self.second);
+        write!(f, "source ranges {first:?} and {second:?} overlap")
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+/// Every value but i64::MAX, which no segment can contain (the ranges exclude their end), so
+/// that every map sends it to itself
+const ALL_BUT_MAX: Range<i64> = i64::MIN..i64::MAX;
+
+/// A function of the integers adding an offset to the values of some disjoint ranges (the
+/// segments), and leaving the other values unchanged. The segments are sorted, with nonzero
+/// offsets, and adjacent segments have different offsets, so that equal maps are equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<(Range<i64>, i64)>,
+}
+
+impl PiecewiseMap {
+    /// The identity (no segments)
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// Builds a map from (source range, offset) segments in any order, which must not overlap
+    pub fn new(
+        segments: impl IntoIterator<Item = (Range<i64>, i64)>,
+    ) -> Result<Self, OverlapError> {
+        let segments = segments
+            .into_iter()
+            .filter(|(range, _)| !range.is_empty())
+            .sorted_by_key(|(range, _)| range.start)
+            .collect_vec();
+        if let Some(pair) = segments
+            .windows(2)
+            .find(|pair| pair[0].0.end > pair[1].0.start)
+        {
+            return Err(OverlapError {
+                first: pair[0].0.clone(),
+                second: pair[1].0.clone(),
+            });
+        }
+        Ok(Self::from_pieces(segments))
+    }
+
+    /// Builds a map from sorted and disjoint pieces, dropping the empty and identity ones and
+    /// merging the adjacent ones with the same offset
+    fn from_pieces(pieces: impl IntoIterator<Item = (Range<i64>, i64)>) -> Self {
+        let mut segments: Vec<(Range<i64>, i64)> = vec![];
+        for (range, offset) in pieces {
+            if range.is_empty() || offset == 0 {
+                continue;
+            }
+            match segments.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end;
+                }
+                _ => segments.push((range, offset)),
+            }
+        }
+        Self { segments }
+    }
+
+    pub fn segments(&self) -> &[(Range<i64>, i64)] {
+        &self.segments
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        let index = self
+            .segments
+            .partition_point(|(range, _)| range.end <= value);
+        match self.segments.get(index) {
+            Some((range, offset)) if range.contains(&value) => value + offset,
+            _ => value,
+        }
+    }
+
+    /// Cuts the range into pieces on which the map adds a single offset, with these offsets
+    /// (0 in the gaps between the segments, and before or after them)
+    pub fn pieces(&self, range: Range<i64>) -> Vec<(Range<i64>, i64)> {
+        let mut pieces = vec![];
+        let mut cursor = range.start;
+        let first = self
+            .segments
+            .partition_point(|(segment, _)| segment.end <= range.start);
+        for (segment, offset) in &self.segments[first..] {
+            if segment.start >= range.end {
+                break;
+            }
+            if segment.start > cursor {
+                pieces.push((cursor..segment.start, 0));
+                cursor = segment.start;
+            }
+            let end = segment.end.min(range.end);
+            pieces.push((cursor..end, *offset));
+            cursor = end;
+        }
+        if cursor < range.end {
+            pieces.push((cursor..range.end, 0));
+        }
+        pieces
+    }
+
+    /// The image of the range, as disjoint ranges sorted by start
+    pub fn apply_range(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        let mut image = self
+            .pieces(range)
+            .into_iter()
+            .map(|(piece, offset)| piece.start + offset..piece.end + offset)
+            .sorted_by_key(|piece| piece.start)
+            .collect_vec();
+        image.dedup_by(|next, previous| {
+            if next.start <= previous.end {
+                previous.end = previous.end.max(next.end);
+                true
+            } else {
+                false
+            }
+        });
+        image
+    }
+
+    /// The map applying this one, then the other
+    pub fn then(&self, other: &PiecewiseMap) -> PiecewiseMap {
+        Self::from_pieces(
+            self.pieces(ALL_BUT_MAX)
+                .into_iter()
+                .flat_map(|(piece, offset)| {
+                    other
+                        .pieces(piece.start + offset..piece.end + offset)
+                        .into_iter()
+                        .map(move |(image, other_offset)| {
+                            (
+                                image.start - offset..image.end - offset,
+                                offset + other_offset,
+                            )
+                        })
+                })
+                .sorted_by_key(|(piece, _)| piece.start),
+        )
+    }
+
+    /// The values sent to the value, in increasing order (none, one or several, as the map may
+    /// not be injective)
+    pub fn preimage(&self, value: i64) -> Vec<i64> {
+        self.pieces(ALL_BUT_MAX)
+            .into_iter()
+            .filter(|(piece, offset)| piece.contains(&(value - offset)))
+            .map(|(_, offset)| value - offset)
+            .chain((value == i64::MAX).then_some(value))
+            .collect()
+    }
+
+    /// The inverse map, if the map is a bijection
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let images = self
+            .pieces(ALL_BUT_MAX)
+            .into_iter()
+            .map(|(piece, offset)| (piece.start + offset..piece.end + offset, -offset))
+            .sorted_by_key(|(image, _)| image.start)
+            .collect_vec();
+        let is_bijection = images
+            .windows(2)
+            .all(|pair| pair[0].0.end == pair[1].0.start);
+        is_bijection.then(|| Self::from_pieces(images))
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (range, offset) in &self.segments {
+            let (start, end) = (range.start, range.end);
+            writeln!(
+                f,
+                "{start}..{end} -> {}..{} ({offset:+})",
+                start + offset,
+                end + offset
+            )?;
         }
+        write!(f, "anything else -> itself")
     }
-    source
 }
 
-fn get_target_ranges(source_ranges: Vec<Range<i64>>, maps: &[ElfMap]) -> Vec<Range<i64>> {
-    // For part 2, each list of ranges gets mapped to another list of ranges
-    source_ranges
+impl TryFrom<&[ElfMap]> for PiecewiseMap {
+    type Error = OverlapError;
+
+    fn try_from(maps: &[ElfMap]) -> Result<Self, OverlapError> {
+        Self::new(
+            maps.iter()
+                .map(|map| (map.source_range.clone(), map.offset)),
+        )
+    }
+}
+
+/// The seeds, and the maps of the categories, one after the other
+pub fn parse_layers(input: &str) -> Result<(Vec<i64>, Vec<PiecewiseMap>), OverlapError> {
+    let (seeds, all_maps) = parse_almanac(input);
+    let layers = all_maps
         .iter()
-        .flat_map(|source_range| {
-            let overlapping: Vec<&ElfMap> = maps
-                .iter()
-                .filter(|&map| {
-                    map.source_range.start < source_range.end
-                        && map.source_range.end > source_range.start
-                })
-                .collect();
-            overlapping
-                .iter()
-                .filter_map(|&map| {
-                    let start = map.source_range.start.max(source_range.start) + map.offset;
-                    let end = map.source_range.end.min(source_range.end) + map.offset;
-                    if end > start {
-                        Some(start..end)
-                    } else {
-                        None
-                    }
-                })
-                .chain(overlapping.windows(2).filter_map(|map_pair| {
-                    // these are the spaces between the ranges, with offset 0
-                    let start = map_pair[0].source_range.end.max(source_range.start);
-                    let end = map_pair[1].source_range.start.min(source_range.end);
-                    if end > start {
-                        Some(start..end)
-                    } else {
-                        None
-                    }
-                }))
-                .collect::<Vec<Range<i64>>>()
-        })
-        .collect()
+        .map(|maps| PiecewiseMap::try_from(maps.as_slice()))
+        .collect::<Result<_, _>>()?;
+    Ok((seeds, layers))
 }
 
-pub fn run(input: &str) {
-    let mut parts = input.split("\n\n");
-    let seeds: Vec<i64> = parse_ints(parts.next().unwrap(), false);
-    let all_maps: Vec<Vec<ElfMap>> = parts
-        .map(|m| {
-            m.lines()
-                .skip(1)
-                .map(|line| {
-                    let map_nums: Vec<i64> = parse_ints(line, false);
-                    ElfMap {
-                        source_range: map_nums[1]..(map_nums[1] + map_nums[2]),
-                        offset: map_nums[0] - map_nums[1],
-                    }
-                })
-                .sorted_by(|a, b| a.source_range.start.cmp(&b.source_range.start))
-                .collect()
-        })
-        .collect();
+/// The seeds, and the maps of all the categories composed into a single seed-to-location map
+pub fn seed_to_location(input: &str) -> Result<(Vec<i64>, PiecewiseMap), OverlapError> {
+    let (seeds, layers) = parse_layers(input)?;
+    let map = layers
+        .iter()
+        .fold(PiecewiseMap::identity(), |map, layer| map.then(layer));
+    Ok((seeds, map))
+}
+
+fn parse_almanac(input: &str) -> (Vec<i64>, Vec<Vec<ElfMap>>) {
+    let seeds = preceded(literal("seeds: "), separated(integer(), literal(" ")));
+    let elf_map = map(
+        pair(
+            terminated(integer(), literal(" ")),
+            pair(terminated(integer(), literal(" ")), integer()),
+        ),
+        |(destination, (source, length)): (i64, (i64, i64))| ElfMap {
+            source_range: source..(source + length),
+            offset: destination - source,
+        },
+    );
+    let header = pair(separated(identifier(), literal("-to-")), literal(" map:"));
+    // a category may have no maps at all
+    let maps = map(
+        preceded(
+            header,
+            optional(preceded(literal("\n"), separated(elf_map, literal("\n")))),
+        ),
+        Option::unwrap_or_default,
+    );
+    let mut blocks = blocks(input);
+    let seeds = parse_all(seeds, blocks.next().unwrap()).expect("Invalid seeds");
+    let layers = blocks
+        .map(|block| parse_all(&maps, block))
+        .collect::<Result<_, _>>()
+        .expect("Invalid maps");
+    (seeds, layers)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let (seeds, layers) =
+        parse_layers(input).unwrap_or_else(|error| panic!("Invalid almanac: {error}"));
     let closest_location = seeds
         .iter()
-        .map(|&seed| {
-            all_maps
-                .iter()
-                .fold(seed, |seed, maps| get_seed_destination(maps, seed))
-        })
+        .map(|&seed| layers.iter().fold(seed, |value, layer| layer.apply(value)))
         .min()
         .unwrap();
-    println!("{}", closest_location);
+    answers.push(closest_location.to_string());
 
     let seed_ranges = seeds
         .iter()
         .tuples::<(_, _)>()
         .map(|(&start, &length)| start..(start + length))
-        .collect();
-
-    let closest_location = all_maps
+        .collect_vec();
+    let closest_location = layers
         .iter()
-        .fold(seed_ranges, |source_ranges, maps| {
-            get_target_ranges(source_ranges, maps)
+        .fold(seed_ranges, |ranges, layer| {
+            ranges
+                .into_iter()
+                .flat_map(|range| layer.apply_range(range))
+                .collect()
         })
         .iter()
         .map(|range| range.start)
         .min()
         .unwrap();
-    println!("{}", closest_location);
+    answers.push(closest_location.to_string());
+    answers
 }
@@ -1,16 +1,54 @@
 use crate::utils::parse_int_vecs;
+use num::bigint::BigInt;
+use num::integer::Roots;
+use num::Integer;
+use std::ops::RangeInclusive;
 
-fn number_of_ways_to_beat_record(race_time: i64, record_distance: i64) -> i64 {
-    // To beat the record distance, we need:
-    // push_time * (race_time - push_time) ≥ record_distance + 1
-    // Solving this mathematically, we get:
-    let discriminant = ((race_time * race_time - 4 * (record_distance + 1)) as f64).sqrt();
-    let min_time = ((race_time as f64 - discriminant) / 2.0).ceil() as i64;
-    let max_time = ((race_time as f64 + discriminant) / 2.0).floor() as i64;
-    max_time - min_time + 1
+/// The exact range of button times beating the record, if any. The distance is
+/// push_time * (race_time - push_time), so the bounds are the roots of
+/// push_time² - race_time * push_time + record_distance, found with an integer square root and
+/// then checked against the record, moving them by one if needed. T must hold race_time² (and
+/// 4 * record_distance) without overflowing, so BigInt is the safe choice for arbitrary inputs.
+pub fn winning_push_times<T>(race_time: &T, record_distance: &T) -> Option<RangeInclusive<T>>
+where
+    T: Integer + Roots + Clone + From<u8>,
+{
+    let beats_record = |push_time: &T| {
+        push_time.clone() * (race_time.clone() - push_time.clone()) > *record_distance
+    };
+    let discriminant = race_time.clone() * race_time.clone() - T::from(4) * record_distance.clone();
+    if discriminant < T::zero() {
+        return None;
+    }
+    let best_time = race_time.clone() / T::from(2);
+    let mut min_time = (race_time.clone() - discriminant.sqrt()) / T::from(2);
+    if min_time < T::zero() {
+        min_time = T::zero();
+    }
+    while !beats_record(&min_time) {
+        if min_time >= best_time {
+            return None;
+        }
+        min_time = min_time + T::one();
+    }
+    while min_time > T::zero() && beats_record(&(min_time.clone() - T::one())) {
+        min_time = min_time - T::one();
+    }
+    // the distance is symmetric around race_time / 2
+    let max_time = race_time.clone() - min_time.clone();
+    Some(min_time..=max_time)
 }
 
-fn parse_number_ignoring_whitespaces(line: &str) -> i64 {
+pub fn number_of_ways_to_beat_record<T>(race_time: &T, record_distance: &T) -> T
+where
+    T: Integer + Roots + Clone + From<u8>,
+{
+    winning_push_times(race_time, record_distance).map_or(T::zero(), |times| {
+        times.end().clone() - times.start().clone() + T::one()
+    })
+}
+
+fn parse_number_ignoring_whitespaces(line: &str) -> BigInt {
     line.split(':')
         .nth(1)
         .unwrap()
@@ -19,26 +57,25 @@ fn parse_number_ignoring_whitespaces(line: &str) -> i64 {
         .unwrap()
 }
 
-pub fn run(input: &str) {
-    let numbers: Vec<Vec<i64>> = parse_int_vecs(input, false);
-    let races: Vec<(i64, i64)> = numbers[0]
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    // the squared race times overflow 64-bit integers above about 3e9
+    let numbers: Vec<Vec<BigInt>> = parse_int_vecs(input, false);
+    let n_ways = numbers[0]
         .iter()
         .zip(numbers[1].iter())
-        .map(|(&a, &b)| (a, b))
-        .collect();
-    let n_ways = races
-        .iter()
         .map(|(race_time, record_distance)| {
-            number_of_ways_to_beat_record(*race_time, *record_distance)
+            number_of_ways_to_beat_record(race_time, record_distance)
         })
-        .reduce(|a, b| a * b)
-        .unwrap();
-    println!("{}", n_ways);
+        .product::<BigInt>();
+    answers.push(n_ways.to_string());
 
+    // the concatenated numbers can get arbitrarily large
     let mut lines = input.lines();
     let race_time = parse_number_ignoring_whitespaces(lines.next().unwrap());
     let record_distance = parse_number_ignoring_whitespaces(lines.next().unwrap());
 
-    let n_ways = number_of_ways_to_beat_record(race_time, record_distance);
-    println!("{}", n_ways);
+    let n_ways = number_of_ways_to_beat_record(&race_time, &record_distance);
+    answers.push(n_ways.to_string());
+    answers
 }
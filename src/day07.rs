@@ -1,54 +1,119 @@
-use crate::utils::argmax;
-
-fn card_rank(card: char, with_jokers: bool) -> usize {
-    let order = if with_jokers {
-        "J23456789TQKA"
-    } else {
-        "23456789TJQKA"
-    };
-    order.chars().position(|c| c == card).unwrap()
+/// A category of hands, with the smallest numbers of cards sharing a label that it needs, most
+/// frequent label first, e.g. [3, 2] for a full house
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub name: String,
+    pub pattern: Vec<usize>,
 }
 
-fn hand_type(hand: &str, with_jokers: bool) -> i64 {
-    let mut card_counts = [0i64; 13];
-    for c in hand.chars() {
-        card_counts[card_rank(c, with_jokers)] += 1;
+impl Category {
+    pub fn new(name: &str, pattern: &[usize]) -> Self {
+        Self {
+            name: name.to_string(),
+            pattern: pattern.to_vec(),
+        }
     }
-    let mut n_jokers = 0;
-    if with_jokers {
-        n_jokers = card_counts[0];
-        card_counts[0] = 0;
+}
+
+/// How hands are ranked: first by category, then card by card. The wildcards count as the most
+/// frequent other label when choosing the category, which gives the best one for tables ordered
+/// like poker's, but they keep their own rank when comparing cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandRules {
+    /// The card labels, weakest first
+    pub rank_order: Vec<u8>,
+    pub hand_size: usize,
+    pub wildcards: Vec<u8>,
+    /// The categories, weakest first. A hand is in the strongest one whose pattern it matches.
+    pub categories: Vec<Category>,
+}
+
+impl HandRules {
+    /// The categories of 5-card Camel Cards
+    pub fn camel_categories() -> Vec<Category> {
+        vec![
+            Category::new("high card", &[1]),
+            Category::new("one pair", &[2]),
+            Category::new("two pair", &[2, 2]),
+            Category::new("three of a kind", &[3]),
+            Category::new("full house", &[3, 2]),
+            Category::new("four of a kind", &[4]),
+            Category::new("five of a kind", &[5]),
+        ]
     }
-    let rank_of_most_frequent = argmax(&card_counts).unwrap();
-    if with_jokers {
-        card_counts[rank_of_most_frequent] += n_jokers;
+
+    /// The rules of part 1
+    pub fn camel_cards() -> Self {
+        Self {
+            rank_order: b"23456789TJQKA".to_vec(),
+            hand_size: 5,
+            wildcards: vec![],
+            categories: Self::camel_categories(),
+        }
     }
-    let n_pairs = card_counts.into_iter().filter(|&c| c == 2).count() as i64;
-    match card_counts[rank_of_most_frequent] {
-        5 => 6,
-        4 => 5,
-        3 => 3 + n_pairs,
-        2 => n_pairs,
-        1 => 0,
-        _ => unreachable!(),
+
+    /// The rules of part 2, where J is a joker, and the weakest card
+    pub fn jokers() -> Self {
+        Self {
+            rank_order: b"J23456789TQKA".to_vec(),
+            wildcards: vec![b'J'],
+            ..Self::camel_cards()
+        }
     }
-}
 
-fn compare_hands(h1: &str, h2: &str, with_jokers: bool) -> std::cmp::Ordering {
-    let type_cmp = hand_type(h1, with_jokers).cmp(&hand_type(h2, with_jokers));
-    if type_cmp != std::cmp::Ordering::Equal {
-        return type_cmp;
+    fn rank(&self, card: u8) -> usize {
+        self.rank_order
+            .iter()
+            .position(|&label| label == card)
+            .unwrap_or_else(|| panic!("Invalid card {}", card as char))
     }
-    for (c1, c2) in h1.chars().zip(h2.chars()) {
-        if c1 != c2 {
-            return card_rank(c1, with_jokers).cmp(&card_rank(c2, with_jokers));
+
+    /// The index of the category of the hand in the table
+    pub fn category(&self, hand: &[u8]) -> usize {
+        assert_eq!(hand.len(), self.hand_size, "Invalid hand size");
+        // the slots of the wildcards stay at 0, which is harmless: they end up last after the
+        // sort, where no pattern needs them, and they make sure counts[0] exists even for a hand
+        // of wildcards only
+        let mut counts = vec![0; self.rank_order.len()];
+        let mut num_wildcards = 0;
+        for &card in hand {
+            if self.wildcards.contains(&card) {
+                num_wildcards += 1;
+            } else {
+                counts[self.rank(card)] += 1;
+            }
         }
+        counts.sort_by(|a, b| b.cmp(a));
+        counts[0] += num_wildcards;
+        self.categories
+            .iter()
+            .rposition(|category| {
+                category.pattern.len() <= counts.len()
+                    && category
+                        .pattern
+                        .iter()
+                        .zip(&counts)
+                        .all(|(needed, count)| count >= needed)
+            })
+            .expect("Hand in no category")
     }
-    unreachable!(); // std::cmp::Ordering::Equal
+
+    /// A key ordering the hands from weakest to strongest: the category, then the rank of
+    /// every card
+    pub fn sort_key(&self, hand: &[u8]) -> Vec<usize> {
+        let mut key = vec![self.category(hand)];
+        key.extend(hand.iter().map(|&card| self.rank(card)));
+        key
+    }
+}
+
+/// Sorts the hands from weakest to strongest, computing each key only once
+pub fn sort_hands(hands: &mut [(String, i64)], rules: &HandRules) {
+    hands.sort_by_cached_key(|(hand, _)| rules.sort_key(hand.as_bytes()));
 }
 
-fn sort_hands_and_get_total_winnings(hands: &mut [(String, i64)], with_jokers: bool) -> i64 {
-    hands.sort_by(|(h1, _), (h2, _)| compare_hands(h1, h2, with_jokers));
+fn sort_hands_and_get_total_winnings(hands: &mut [(String, i64)], rules: &HandRules) -> i64 {
+    sort_hands(hands, rules);
     hands
         .iter()
         .enumerate()
@@ -56,7 +121,8 @@ fn sort_hands_and_get_total_winnings(hands: &mut [(String, i64)], with_jokers: b
         .sum()
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let mut hands: Vec<(String, i64)> = input
         .lines()
         .map(|line| {
@@ -67,8 +133,9 @@ pub fn run(input: &str) {
             )
         })
         .collect();
-    for with_jokers in [false, true] {
-        let total_winnings = sort_hands_and_get_total_winnings(&mut hands, with_jokers);
-        println!("{}", total_winnings);
+    for rules in [HandRules::camel_cards(), HandRules::jokers()] {
+        let total_winnings = sort_hands_and_get_total_winnings(&mut hands, &rules);
+        answers.push(total_winnings.to_string());
     }
+    answers
 }
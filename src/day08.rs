@@ -1,5 +1,10 @@
+use crate::utils::dot::Dot;
 use crate::utils::lcm;
+use crate::utils::parse::{
+    delimited, identifier, key_value, literal, pair, parse_str, separated, terminated,
+};
 use hashbrown::HashMap;
+use std::collections::BTreeMap;
 
 fn get_number_of_steps(
     start: &str,
@@ -36,17 +41,56 @@ fn get_number_of_parallel_steps(directions: &[u8], nodes: &HashMap<&str, [&str;
     z_intervals.reduce(lcm).unwrap()
 }
 
-pub fn run(input: &str) {
-    let mut parts = input.split("\n\n");
-    let directions = parts.next().unwrap().as_bytes();
-    let nodes: HashMap<_, _> = parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| (&line[0..3], [&line[7..10], &line[12..15]]))
+fn parse_input(input: &str) -> (&[u8], HashMap<&str, [&str; 2]>) {
+    let node = key_value(
+        identifier(),
+        literal(" = "),
+        delimited(
+            literal("("),
+            pair(terminated(identifier(), literal(", ")), identifier()),
+            literal(")"),
+        ),
+    );
+    let network = pair(
+        terminated(identifier(), literal("\n\n")),
+        separated(node, literal("\n")),
+    );
+    let (directions, nodes) = parse_str(network, input).expect("Invalid input");
+    let nodes = nodes
+        .into_iter()
+        .map(|(name, (left, right))| (name, [left, right]))
         .collect();
+    (directions.as_bytes(), nodes)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let (directions, nodes) = parse_input(input);
     let n_steps = get_number_of_steps("AAA", "ZZZ", directions, &nodes);
-    println!("{}", n_steps);
+    answers.push(n_steps.to_string());
     let n_steps = get_number_of_parallel_steps(directions, &nodes);
-    println!("{}", n_steps)
+    answers.push(n_steps.to_string());
+    answers
+}
+
+/// The network, with the start nodes in green and the end nodes in red
+pub fn to_dot(input: &str) -> Dot {
+    let (_, nodes) = parse_input(input);
+    // sorted by name, so that the output doesn't depend on the hash map's order
+    let nodes: BTreeMap<_, _> = nodes.into_iter().collect();
+    let mut dot = Dot::directed();
+    for (name, [left, right]) in nodes {
+        if name.ends_with('A') {
+            dot.node(name, &[("shape", "doublecircle"), ("color", "green")]);
+        } else if name.ends_with('Z') {
+            dot.node(name, &[("shape", "doublecircle"), ("color", "red")]);
+        }
+        if left == right {
+            dot.edge(name, left, &[("label", "L/R")]);
+        } else {
+            dot.edge(name, left, &[("label", "L")]);
+            dot.edge(name, right, &[("label", "R")]);
+        }
+    }
+    dot
 }
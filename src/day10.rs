@@ -1,4 +1,6 @@
-use hashbrown::HashMap;
+use crate::utils::grid::Grid;
+use crate::utils::polygon::{self, Location};
+use crate::utils::viz::{Animation, Color, Scene, Style};
 
 fn get_start_position(pipes: &[&[u8]]) -> (i32, i32) {
     for (y, row) in pipes.iter().enumerate() {
@@ -19,33 +21,21 @@ fn get_main_loop(
     start_y: i32,
     mut dx: i32,
     mut dy: i32,
-) -> Option<HashMap<(i32, i32), u8>> {
+) -> Option<Vec<(i32, i32)>> {
     let mut x = start_x;
     let mut y = start_y;
-    let start_dx = dx;
-    let start_dy = dy;
-    let mut main_loop: HashMap<(i32, i32), u8> = HashMap::new();
+    let mut main_loop = vec![];
     loop {
         x += dx;
         y += dy;
         if x < 0 || x >= width || y < 0 || y >= height {
             return None;
         }
+        main_loop.push((x, y));
         let c = pipes[y as usize][x as usize];
         if c == b'S' {
-            let c = match (start_dx, start_dy, dx, dy) {
-                (1, 0, 1, 0) => b'-',
-                (1, 0, 0, 1) => b'L',
-                (1, 0, 0, -1) => b'F',
-                (0, 1, 1, 0) => b'7',
-                (0, 1, 0, 1) => b'|',
-                (-1, 0, 0, 1) => b'J',
-                _ => unreachable!(),
-            };
-            main_loop.insert((x, y), c);
             return Some(main_loop);
         }
-        main_loop.insert((x, y), c);
         (dx, dy) = match (dx, dy, c) {
             (1, 0, b'-') | (0, 1, b'L') | (0, -1, b'F') => (1, 0),
             (1, 0, b'7') | (0, 1, b'|') | (-1, 0, b'F') => (0, 1),
@@ -58,35 +48,76 @@ fn get_main_loop(
     }
 }
 
-fn count_tiles_inside(main_loop: &HashMap<(i32, i32), u8>, width: i32, height: i32) -> u32 {
-    let mut num_tiles_inside = 0;
-    for y in 0..height {
-        let mut is_inside = false;
-        for x in 0..width {
-            let c = main_loop.get(&(x, y));
-            if let Some(&c) = c {
-                if c == b'|' || c == b'J' || c == b'L' {
-                    is_inside = !is_inside;
-                }
-            } else if is_inside {
-                num_tiles_inside += 1;
-            }
-        }
-    }
-    num_tiles_inside
+fn count_tiles_inside(main_loop: &[(i32, i32)]) -> i64 {
+    let vertices: Vec<_> = main_loop
+        .iter()
+        .map(|&(x, y)| (x as i64, y as i64))
+        .collect();
+    polygon::interior_points(&vertices)
 }
 
-pub fn run(input: &str) {
-    let pipes: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+fn find_main_loop(pipes: &[&[u8]]) -> Vec<(i32, i32)> {
     let width = pipes[0].len() as i32;
     let height = pipes.len() as i32;
-    let (start_x, start_y) = get_start_position(&pipes);
-    let main_loop = [(1, 0), (0, 1), (-1, 0), (0, -1)]
+    let (start_x, start_y) = get_start_position(pipes);
+    [(1, 0), (0, 1), (-1, 0), (0, -1)]
         .iter()
-        .filter_map(|&(dx, dy)| get_main_loop(&pipes, width, height, start_x, start_y, dx, dy))
+        .filter_map(|&(dx, dy)| get_main_loop(pipes, width, height, start_x, start_y, dx, dy))
         .next()
-        .unwrap();
-    println!("{}", main_loop.len() / 2);
-    let num_tiles_inside = count_tiles_inside(&main_loop, width, height);
-    println!("{}", num_tiles_inside);
+        .unwrap()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let pipes: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let main_loop = find_main_loop(&pipes);
+    answers.push((main_loop.len() / 2).to_string());
+    let num_tiles_inside = count_tiles_inside(&main_loop);
+    answers.push(num_tiles_inside.to_string());
+    answers
+}
+
+fn pipe_symbol(&c: &u8) -> char {
+    match c {
+        b'|' => '│',
+        b'-' => '─',
+        b'L' => '└',
+        b'J' => '┘',
+        b'7' => '┐',
+        b'F' => '┌',
+        b'.' => ' ',
+        _ => c as char,
+    }
+}
+
+pub fn visualize(input: &str) -> Animation {
+    let grid = Grid::parse(input);
+    let pipes: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let main_loop: Vec<_> = find_main_loop(&pipes)
+        .into_iter()
+        .map(|(x, y)| (x as usize, y as usize))
+        .collect();
+    let loop_style = Style::foreground(Color::Green);
+    let mut animation = Animation::new();
+    let step = (main_loop.len() / 50).max(1);
+    for length in (step..main_loop.len()).step_by(step) {
+        let scene =
+            Scene::new(&grid, pipe_symbol).overlay(main_loop[..length].to_vec(), loop_style);
+        animation.record(&scene);
+    }
+    let vertices: Vec<_> = main_loop
+        .iter()
+        .map(|&(x, y)| (x as i64, y as i64))
+        .collect();
+    let tiles_inside = (0..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| polygon::locate(&vertices, (x as i64, y as i64)) == Location::Inside);
+    let scene = Scene::new(&grid, pipe_symbol)
+        .overlay(main_loop.clone(), loop_style)
+        .overlay(
+            tiles_inside,
+            Style::background(Color::Yellow).with_symbol(' '),
+        );
+    animation.record(&scene);
+    animation
 }
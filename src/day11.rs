@@ -48,7 +48,8 @@ fn sum_of_shortest_distances(galaxies: &[(i32, i32)], expansion_factor: u64) ->
     total_distance
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let galaxies: Vec<(i32, i32)> = input
         .lines()
         .enumerate()
@@ -61,6 +62,7 @@ pub fn run(input: &str) {
         .collect();
     for expansion_factor in [2, 1000000] {
         let total_distance = sum_of_shortest_distances(&galaxies, expansion_factor);
-        println!("{}", total_distance);
+        answers.push(total_distance.to_string());
     }
+    answers
 }
@@ -1,13 +1,19 @@
+use crate::utils::memo::{Memo, MemoStats};
 use crate::utils::parse_ints;
 
+type Record<'a> = (&'a [u8], &'a [usize]);
+
 fn min_size(group_lengths: &[usize]) -> usize {
     group_lengths.iter().map(|len| len + 1).sum::<usize>()
 }
 
-fn count_arrangements(spring_states: &[u8], group_lengths: &[usize]) -> u64 {
+fn count_arrangements<'a>(
+    memo: &Memo<'a, Record<'a>, u64>,
+    (spring_states, group_lengths): Record<'a>,
+) -> u64 {
     // For every possible position of the center group of damaged springs, count the
-    // number of possible arrangements for the left and right groups using recursive
-    // calls, and add their product to the total number of arrangements.
+    // number of possible arrangements for the left and right groups using memoized
+    // recursive calls, and add their product to the total number of arrangements.
 
     if group_lengths.is_empty() {
         if spring_states.contains(&b'#') {
@@ -30,16 +36,12 @@ fn count_arrangements(spring_states: &[u8], group_lengths: &[usize]) -> u64 {
                 && (after_index == spring_states.len()
                     || b".?".contains(&spring_states[after_index]))
             {
-                let left_arrangements = count_arrangements(
-                    &spring_states[..start_index.max(1) - 1],
-                    left_group_lengths,
-                );
+                let left_arrangements =
+                    memo.get((&spring_states[..start_index.max(1) - 1], left_group_lengths));
                 if left_arrangements > 0 {
                     let right_start_index = (after_index + 1).min(spring_states.len());
-                    let right_arrangements = count_arrangements(
-                        &spring_states[right_start_index..],
-                        right_group_lengths,
-                    );
+                    let right_arrangements =
+                        memo.get((&spring_states[right_start_index..], right_group_lengths));
                     num_arrangements += left_arrangements * right_arrangements;
                 }
             }
@@ -48,8 +50,20 @@ fn count_arrangements(spring_states: &[u8], group_lengths: &[usize]) -> u64 {
     num_arrangements
 }
 
-pub fn run(input: &str) {
-    let condition_records: Vec<_> = input
+/// The total number of arrangements of the records, with the statistics of their caches
+fn count_all_arrangements<'a>(records: impl Iterator<Item = Record<'a>>) -> (u64, MemoStats) {
+    let mut total = 0;
+    let mut stats = vec![];
+    for record in records {
+        let memo = Memo::new(count_arrangements);
+        total += memo.get(record);
+        stats.push(memo.stats());
+    }
+    (total, stats.into_iter().sum())
+}
+
+fn parse_records(input: &str) -> Vec<(&[u8], Vec<usize>)> {
+    input
         .lines()
         .map(|line| {
             let mut parts = line.split_ascii_whitespace();
@@ -57,27 +71,44 @@ pub fn run(input: &str) {
             let group_lengths: Vec<usize> = parse_ints(parts.next().unwrap(), false);
             (spring_states, group_lengths)
         })
-        .collect();
+        .collect()
+}
 
-    let num_arrangements: u64 = condition_records
-        .iter()
-        .map(|(spring_states, group_lengths)| count_arrangements(spring_states, group_lengths))
-        .sum();
-    println!("{}", num_arrangements);
+fn unfold((spring_states, group_lengths): &(&[u8], Vec<usize>)) -> (String, Vec<usize>) {
+    let unfolded_spring_states = [std::str::from_utf8(spring_states).unwrap(); 5].join("?");
+    (unfolded_spring_states, group_lengths.repeat(5))
+}
 
-    let unfolded_condition_records =
-        condition_records
-            .iter()
-            .map(|(spring_states, group_lengths)| {
-                let unfolded_spring_states =
-                    [std::str::from_utf8(spring_states).unwrap(); 5].join("?");
-                let unfolded_group_lengths = group_lengths.repeat(5);
-                (unfolded_spring_states, unfolded_group_lengths)
-            });
-    let num_arrangements: u64 = unfolded_condition_records
-        .map(|(spring_states, group_lengths)| {
-            count_arrangements(spring_states.as_bytes(), &group_lengths)
-        })
-        .sum();
-    println!("{}", num_arrangements);
+/// The number of arrangements of the records, then of the unfolded records, with the
+/// statistics of their caches
+fn count_both_parts(input: &str) -> [(u64, MemoStats); 2] {
+    let condition_records = parse_records(input);
+    let unfolded_condition_records: Vec<_> = condition_records.iter().map(unfold).collect();
+    [
+        count_all_arrangements(
+            condition_records
+                .iter()
+                .map(|(spring_states, group_lengths)| (*spring_states, &group_lengths[..])),
+        ),
+        count_all_arrangements(
+            unfolded_condition_records
+                .iter()
+                .map(|(spring_states, group_lengths)| {
+                    (spring_states.as_bytes(), &group_lengths[..])
+                }),
+        ),
+    ]
+}
+
+/// How the memoization of both parts pays off
+pub fn trace(input: &str) -> String {
+    let [(_, stats), (_, unfolded_stats)] = count_both_parts(input);
+    format!("Part 1: {stats}\nPart 2: {unfolded_stats}\n")
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    count_both_parts(input)
+        .iter()
+        .map(|(num_arrangements, _)| num_arrangements.to_string())
+        .collect()
 }
@@ -44,7 +44,8 @@ fn find_reflection_id(grid: &[Vec<bool>], num_reflection_errors: u32) -> u32 {
     unreachable!();
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let grids: Vec<Vec<Vec<bool>>> = input
         .split("\n\n")
         .map(|grid| {
@@ -59,6 +60,7 @@ pub fn run(input: &str) {
             .iter()
             .map(|grid| find_reflection_id(grid, num_reflection_errors))
             .sum();
-        println!("{}", total_reflection_ids);
+        answers.push(total_reflection_ids.to_string());
     }
+    answers
 }
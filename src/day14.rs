@@ -1,74 +1,110 @@
-use crate::utils::compute_hash;
-use std::collections::HashMap;
+use crate::utils::fast_hash::FxBuildHasher;
+use crate::utils::image::Image;
+use crate::utils::{BitGrid, Grid};
+use hashbrown::HashMap;
+use std::hash::BuildHasher;
 
-fn tilt(platform: &mut Vec<Vec<u8>>, dx: i32, dy: i32) {
-    let start_x: usize = if dx == -1 { 1 } else { 0 };
-    let start_y: usize = if dy == -1 { 1 } else { 0 };
-    let end_x = platform[0].len() - if dx == 1 { 1 } else { 0 };
-    let end_y = platform.len() - if dy == 1 { 1 } else { 0 };
+fn shift(grid: &BitGrid, dx: i32, dy: i32) -> BitGrid {
+    match (dx, dy) {
+        (0, -1) => grid.shift_up(),
+        (0, 1) => grid.shift_down(),
+        (-1, 0) => grid.shift_left(),
+        (1, 0) => grid.shift_right(),
+        _ => unreachable!(),
+    }
+}
+
+fn tilt(rounded_rocks: &mut BitGrid, open: &BitGrid, dx: i32, dy: i32) {
+    // every pass moves all the rocks that have an empty cell in front of them, all at once
     loop {
-        let mut num_changes = 0;
-        for x in start_x..end_x {
-            for y in start_y..end_y {
-                let here = platform[y][x];
-                if here == b'O' {
-                    let (there_x, there_y) = ((x as i32 + dx) as usize, (y as i32 + dy) as usize);
-                    let there = platform[there_y][there_x];
-                    if there == b'.' {
-                        platform[y][x] = b'.';
-                        platform[there_y][there_x] = b'O';
-                        num_changes += 1;
-                    }
-                }
-            }
-        }
-        if num_changes == 0 {
+        let empty = open.and_not(rounded_rocks);
+        let moving = &shift(&empty, -dx, -dy) & rounded_rocks;
+        if moving.is_empty() {
             break;
         }
+        rounded_rocks.and_not_assign(&moving);
+        *rounded_rocks |= &shift(&moving, dx, dy);
     }
 }
 
-fn compute_total_load(platform: &[Vec<u8>]) -> u32 {
-    platform
-        .iter()
-        .enumerate()
-        .map(|(y, row)| {
-            let num_rounded_rocks = row.iter().filter(|&&c| c == b'O').count();
-            (num_rounded_rocks * (platform.len() - y)) as u32
-        })
+fn compute_total_load(rounded_rocks: &BitGrid) -> u32 {
+    let height = rounded_rocks.height();
+    (0..height)
+        .map(|y| (rounded_rocks.row_count_ones(y) * (height - y)) as u32)
         .sum()
 }
 
-fn cycle(platform: &mut Vec<Vec<u8>>) {
+fn cycle(rounded_rocks: &mut BitGrid, open: &BitGrid) {
     for (dx, dy) in [(0, -1), (-1, 0), (0, 1), (1, 0)] {
-        tilt(platform, dx, dy);
+        tilt(rounded_rocks, open, dx, dy);
     }
 }
 
-fn repeat_cycles(platform: &mut Vec<Vec<u8>>, num_cycles: u32) {
-    let mut hash_index = HashMap::new();
+fn repeat_cycles<S: BuildHasher + Default>(
+    rounded_rocks: &mut BitGrid,
+    open: &BitGrid,
+    num_cycles: u32,
+) {
+    // the states themselves are the keys, so that two states with the same hash (which a fast
+    // hash makes likely enough) can't pass for a cycle
+    let mut state_index: HashMap<BitGrid, u32, S> = HashMap::default();
     for index in 0.. {
-        let hash = compute_hash(platform);
-        let hash_index = *hash_index.entry(hash).or_insert(index);
-        if hash_index < index {
-            let modulo = index - hash_index;
+        let first_index = *state_index.entry(rounded_rocks.clone()).or_insert(index);
+        if first_index < index {
+            let modulo = index - first_index;
             let remaining_cycles = (num_cycles - index) % modulo;
-            (0..remaining_cycles).for_each(|_| cycle(platform));
+            (0..remaining_cycles).for_each(|_| cycle(rounded_rocks, open));
             return;
         }
-        cycle(platform);
+        cycle(rounded_rocks, open);
     }
     unreachable!()
 }
 
-pub fn run(input: &str) {
-    let mut platform: Vec<Vec<_>> = input.lines().map(|line| line.bytes().collect()).collect();
+/// The solver with another hasher for the map of the states, to compare them
+pub fn solve_with_hasher<S: BuildHasher + Default>(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let platform: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let mut rounded_rocks = BitGrid::from_bytes(&platform, b'O');
+    let open = BitGrid::from_bytes(&platform, b'#').complement();
+
+    tilt(&mut rounded_rocks, &open, 0, -1);
+    let total_load = compute_total_load(&rounded_rocks);
+    answers.push(total_load.to_string());
 
-    tilt(&mut platform, 0, -1);
-    let total_load = compute_total_load(&platform);
-    println!("{}", total_load);
+    repeat_cycles::<S>(&mut rounded_rocks, &open, 1_000_000_000);
+    let total_load = compute_total_load(&rounded_rocks);
+    answers.push(total_load.to_string());
+    answers
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    solve_with_hasher::<FxBuildHasher>(input)
+}
+
+fn platform_image(rounded_rocks: &BitGrid, open: &BitGrid) -> Image {
+    let platform = Grid::from_fn(open.width(), open.height(), |x, y| {
+        if rounded_rocks.get(x, y) {
+            [230, 160, 40]
+        } else if open.get(x, y) {
+            [30, 30, 40]
+        } else {
+            [150, 150, 160]
+        }
+    });
+    Image::from_grid(&platform, |&color| color).scaled(4)
+}
 
-    repeat_cycles(&mut platform, 1_000_000_000);
-    let total_load = compute_total_load(&platform);
-    println!("{}", total_load);
+/// The rocks initially, after the north tilt of part 1, and after the cycles of part 2
+pub fn render(input: &str) -> Vec<(String, Image)> {
+    let platform: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let mut rounded_rocks = BitGrid::from_bytes(&platform, b'O');
+    let open = BitGrid::from_bytes(&platform, b'#').complement();
+    let mut images = vec![("initial".to_string(), platform_image(&rounded_rocks, &open))];
+    let mut tilted_rocks = rounded_rocks.clone();
+    tilt(&mut tilted_rocks, &open, 0, -1);
+    images.push(("north".to_string(), platform_image(&tilted_rocks, &open)));
+    repeat_cycles::<FxBuildHasher>(&mut rounded_rocks, &open, 1_000_000_000);
+    images.push(("cycles".to_string(), platform_image(&rounded_rocks, &open)));
+    images
 }
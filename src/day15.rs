@@ -48,17 +48,19 @@ fn focusing_power(box_number: usize, slot_number: usize, focal_length: u32) -> u
     (box_number as u32 + 1) * (slot_number as u32 + 1) * focal_length
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let sum_of_hashes: u32 = input
         .split(',')
         .map(|step| compute_elf_hash(step.as_bytes()))
         .sum();
-    println!("{}", sum_of_hashes);
+    answers.push(sum_of_hashes.to_string());
 
     let mut boxes: Vec<Vec<(String, u32)>> = vec![vec![]; 256];
     input
         .split(',')
         .for_each(|step| apply_step(&mut boxes, step));
     let sum_of_focusing_powers: u32 = total_focusing_power(&boxes);
-    println!("{}", sum_of_focusing_powers);
+    answers.push(sum_of_focusing_powers.to_string());
+    answers
 }
@@ -1,24 +1,27 @@
+use crate::utils::viz::{Animation, Color, Scene, Style};
+use crate::utils::{BitGrid, Grid};
+
 fn propagate_light_beam(
     grid: &[&[u8]],
     mut x: i32,
     mut y: i32,
     mut dx: i32,
     mut dy: i32,
-    visited_cells: &mut Vec<Vec<u8>>,
+    visited_cells: &mut [BitGrid; 4],
 ) {
     let size = grid.len() as i32;
     while x >= 0 && x < size && y >= 0 && y < size {
         let direction = match (dx, dy) {
-            (0, 1) => 1,
-            (0, -1) => 2,
-            (1, 0) => 4,
-            (-1, 0) => 8,
+            (0, 1) => 0,
+            (0, -1) => 1,
+            (1, 0) => 2,
+            (-1, 0) => 3,
             _ => unreachable!(),
         };
-        if visited_cells[y as usize][x as usize] & direction != 0 {
+        if visited_cells[direction].get(x as usize, y as usize) {
             break;
         }
-        visited_cells[y as usize][x as usize] |= direction;
+        visited_cells[direction].set(x as usize, y as usize, true);
         let c = grid[y as usize][x as usize];
         match c {
             b'.' => {}
@@ -61,32 +64,59 @@ fn propagate_light_beam(
     }
 }
 
-fn count_energized_cells(grid: &[&[u8]], x: i32, y: i32, dx: i32, dy: i32) -> usize {
-    let mut visited_cells = vec![vec![0u8; grid[0].len()]; grid.len()];
+fn energized_cells(grid: &[&[u8]], x: i32, y: i32, dx: i32, dy: i32) -> BitGrid {
+    let mut visited_cells: [BitGrid; 4] =
+        std::array::from_fn(|_| BitGrid::new(grid[0].len(), grid.len()));
     propagate_light_beam(grid, x, y, dx, dy, &mut visited_cells);
-    visited_cells
-        .iter()
-        .map(|row| row.iter().filter(|&&c| c != 0).count())
-        .sum()
+    let [down, up, right, left] = visited_cells;
+    &(&down | &up) | &(&right | &left)
+}
+
+fn count_energized_cells(grid: &[&[u8]], x: i32, y: i32, dx: i32, dy: i32) -> usize {
+    energized_cells(grid, x, y, dx, dy).count_ones()
 }
 
-pub fn run(input: &str) {
+fn beam_entry_points(size: i32) -> impl Iterator<Item = (i32, i32, i32, i32)> {
+    (0..size).flat_map(move |i| {
+        [
+            (i, 0, 0, 1),
+            (i, size - 1, 0, -1),
+            (0, i, 1, 0),
+            (size - 1, i, -1, 0),
+        ]
+    })
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let grid: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
     let num_energized_cells = count_energized_cells(&grid, 0, 0, 1, 0);
-    println!("{:?}", num_energized_cells);
+    answers.push(num_energized_cells.to_string());
 
     let size = grid.len() as i32;
-    let max_energized_cells = (0..size)
-        .flat_map(|i| {
-            [
-                (i, 0, 0, 1),
-                (i, size - 1, 0, -1),
-                (0, i, 1, 0),
-                (size - 1, i, -1, 0),
-            ]
-        })
+    let max_energized_cells = beam_entry_points(size)
         .map(|(x, y, dx, dy)| count_energized_cells(&grid, x, y, dx, dy))
         .max()
         .unwrap();
-    println!("{}", max_energized_cells);
+    answers.push(max_energized_cells.to_string());
+    answers
+}
+
+/// Shows the tiles energized by the beam of part 1, then by the best beam of part 2
+pub fn visualize(input: &str) -> Animation {
+    let rows: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let grid = Grid::parse(input);
+    let size = rows.len() as i32;
+    let best_entry_point = beam_entry_points(size)
+        .max_by_key(|&(x, y, dx, dy)| count_energized_cells(&rows, x, y, dx, dy))
+        .unwrap();
+    let mut animation = Animation::new();
+    for (x, y, dx, dy) in [(0, 0, 1, 0), best_entry_point] {
+        let energized = energized_cells(&rows, x, y, dx, dy);
+        let scene = Scene::new(&grid, |&c| c as char)
+            .overlay(energized.iter_ones(), Style::background(Color::Yellow))
+            .overlay([(x as usize, y as usize)], Style::background(Color::Red));
+        animation.record(&scene);
+    }
+    animation
 }
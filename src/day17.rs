@@ -1,3 +1,6 @@
+use crate::utils::viz::{Animation, Color, Scene, Style};
+use crate::utils::Grid;
+
 use pathfinding::directed::astar::astar;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -69,23 +72,50 @@ impl Crucible {
     }
 }
 
-fn find_min_heat_loss(heat_loss_map: &[&[u8]], is_ultra: bool) -> i32 {
+/// The crucible positions along the best path, and the total heat loss on that path
+fn find_best_path(heat_loss_map: &[&[u8]], is_ultra: bool) -> (Vec<Crucible>, i32) {
     let width = heat_loss_map[0].len() as i32;
     let height = heat_loss_map[0].len() as i32;
-    let (_, total_heat_loss) = astar(
+    astar(
         &Crucible::default(),
         |n| n.successors(heat_loss_map, width, height, is_ultra),
         |n| n.heuristic(width, height, is_ultra),
         |n| n.success(width, height, is_ultra),
     )
-    .unwrap();
-    total_heat_loss
+    .unwrap()
+}
+
+fn find_min_heat_loss(heat_loss_map: &[&[u8]], is_ultra: bool) -> i32 {
+    find_best_path(heat_loss_map, is_ultra).1
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let heat_loss_map: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
     for is_ultra in [false, true] {
         let min_heat_loss = find_min_heat_loss(&heat_loss_map, is_ultra);
-        println!("{min_heat_loss}");
+        answers.push(min_heat_loss.to_string());
+    }
+    answers
+}
+
+/// Draws the best paths of the normal and ultra crucibles, one step at a time
+pub fn visualize(input: &str) -> Animation {
+    let heat_loss_map: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let grid = Grid::parse(input);
+    let mut animation = Animation::new();
+    for (is_ultra, color) in [(false, Color::Red), (true, Color::Cyan)] {
+        let (path, _) = find_best_path(&heat_loss_map, is_ultra);
+        let cells: Vec<_> = path
+            .iter()
+            .map(|crucible| (crucible.x as usize, crucible.y as usize))
+            .collect();
+        let step = (cells.len() / 50).max(1);
+        for length in (1..cells.len()).step_by(step).chain([cells.len()]) {
+            let scene = Scene::new(&grid, |&c| c as char)
+                .overlay(cells[..length].to_vec(), Style::background(color));
+            animation.record(&scene);
+        }
     }
+    animation
 }
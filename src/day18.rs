@@ -1,73 +1,29 @@
-use std::collections::HashSet;
+use crate::utils::image::{Image, Rgb};
+use crate::utils::polygon::{self, Location};
+use crate::utils::{FastMap, Grid};
 
-fn get_vertical_trenches_and_ys(dig_plan: &[(u8, i32)]) -> (Vec<(i32, i32, i32)>, Vec<i32>) {
-    let mut vertical_trenches = vec![];
-    let mut x = 0;
-    let mut y = 0;
-    let mut ys = HashSet::from([y]);
-    for &(direction, num_steps) in dig_plan {
-        let previous_y = y;
-        match direction {
-            b'R' => {
-                x += num_steps;
-            }
-            b'L' => {
-                x -= num_steps;
-            }
-            b'D' => {
-                y += num_steps;
-                vertical_trenches.push((x, previous_y, y));
-            }
-            b'U' => {
-                y -= num_steps;
-                vertical_trenches.push((x, y, previous_y));
-            }
-            _ => unreachable!(),
-        };
-        ys.insert(y);
+fn direction_step(direction: u8) -> (i64, i64) {
+    match direction {
+        b'R' => (1, 0),
+        b'D' => (0, 1),
+        b'L' => (-1, 0),
+        b'U' => (0, -1),
+        _ => unreachable!(),
     }
-    assert!(x == 0 && y == 0); // ensure this is a loop (I assume trenches don't cross)
-    vertical_trenches.sort();
-    let mut ys = Vec::from_iter(ys);
-    ys.sort();
-    (vertical_trenches, ys)
+}
+
+fn dig_trench(dig_plan: &[(u8, i32)]) -> Vec<polygon::Point> {
+    polygon::trace(
+        dig_plan
+            .iter()
+            .map(|&(direction, num_steps)| (direction_step(direction), num_steps as i64)),
+    )
 }
 
 fn compute_lagoon_volume(dig_plan: &[(u8, i32)]) -> i64 {
-    let (vertical_trenches, ys) = get_vertical_trenches_and_ys(dig_plan);
-    ys.iter()
-        .map(|&y| y..y + 1)
-        .chain(
-            ys.iter()
-                .zip(ys.iter().skip(1))
-                .map(|(&y, &next_y)| y + 1..next_y),
-        )
-        .map(|y_range| {
-            let mut width_inside: i64 = 0;
-            let mut is_odd_crossing = false;
-            let mut is_border = false;
-            let mut is_inside = false;
-            for &(x, y1, y2) in &vertical_trenches {
-                if y1 > y_range.start || y2 < y_range.end - 1 {
-                    continue;
-                }
-                if y2 > y_range.start {
-                    is_odd_crossing = !is_odd_crossing;
-                }
-                is_border = !is_border && (y1 == y_range.start || y2 == y_range.start);
-                if is_inside {
-                    if !(is_odd_crossing || is_border) {
-                        is_inside = false;
-                        width_inside += (x + 1) as i64;
-                    }
-                } else if is_odd_crossing || is_border {
-                    is_inside = true;
-                    width_inside -= x as i64;
-                }
-            }
-            y_range.len() as i64 * width_inside
-        })
-        .sum()
+    let trench = dig_trench(dig_plan);
+    assert!(trench.last() == Some(&(0, 0))); // ensure this is a loop (I assume trenches don't cross)
+    polygon::lattice_points(&trench)
 }
 
 fn parse_dig_plan_1(line: &str) -> (u8, i32) {
@@ -93,10 +49,48 @@ fn parse_dig_plan_2(line: &str) -> (u8, i32) {
     (b"RDLU"[(hex % 16) as usize], hex / 16)
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     for parse_fn in [parse_dig_plan_1, parse_dig_plan_2] {
         let dig_plan: Vec<_> = input.lines().map(parse_fn).collect();
         let lagoon_volume = compute_lagoon_volume(&dig_plan);
-        println!("{}", lagoon_volume);
+        answers.push(lagoon_volume.to_string());
+    }
+    answers
+}
+
+fn parse_color(line: &str) -> Rgb {
+    let hex = line.split('#').nth(1).unwrap();
+    std::array::from_fn(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+}
+
+/// The lagoon of part 1, with the trench painted in the colors of the dig plan
+pub fn render(input: &str) -> Vec<(String, Image)> {
+    let dig_plan: Vec<_> = input.lines().map(parse_dig_plan_1).collect();
+    let mut position = (0, 0);
+    let mut trench_colors = FastMap::default();
+    for (&(direction, num_steps), line) in dig_plan.iter().zip(input.lines()) {
+        let (dx, dy) = direction_step(direction);
+        for _ in 0..num_steps {
+            position = (position.0 + dx, position.1 + dy);
+            trench_colors.insert(position, parse_color(line));
+        }
     }
+    let trench = dig_trench(&dig_plan);
+    let min_x = trench.iter().map(|&(x, _)| x).min().unwrap();
+    let max_x = trench.iter().map(|&(x, _)| x).max().unwrap();
+    let min_y = trench.iter().map(|&(_, y)| y).min().unwrap();
+    let max_y = trench.iter().map(|&(_, y)| y).max().unwrap();
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+    let lagoon = Grid::from_fn(width, height, |x, y| {
+        let point = (x as i64 + min_x, y as i64 + min_y);
+        match trench_colors.get(&point) {
+            Some(&color) => color,
+            None if polygon::locate(&trench, point) == Location::Inside => [64, 64, 64],
+            None => [255, 255, 255],
+        }
+    });
+    let image = Image::from_grid(&lagoon, |&color| color).scaled(2);
+    vec![("lagoon".to_string(), image)]
 }
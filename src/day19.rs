@@ -1,9 +1,30 @@
-use crate::utils::parse_ints;
+use crate::utils::dot::Dot;
+use crate::utils::parse::{
+    alt, blocks, delimited, identifier, integer, key_value, literal, map, pair, parse_all,
+    separated, terminated, Parser,
+};
 use hashbrown::HashMap;
+use std::collections::BTreeMap;
 
-fn parse_part_rating(line: &str) -> [i32; 4] {
-    let ratings = parse_ints(line, false);
-    [ratings[0], ratings[1], ratings[2], ratings[3]] // x m a s
+fn var_index(var_name: &str) -> usize {
+    ["x", "m", "a", "s"]
+        .iter()
+        .position(|&name| name == var_name)
+        .unwrap()
+}
+
+fn part_rating_parser<'a>() -> impl Parser<'a, [i32; 4]> {
+    let rating = key_value(identifier(), literal("="), integer());
+    map(
+        delimited(literal("{"), separated(rating, literal(",")), literal("}")),
+        |ratings| {
+            let mut part_rating = [0; 4];
+            for (var_name, value) in ratings {
+                part_rating[var_index(var_name)] = value;
+            }
+            part_rating
+        },
+    )
 }
 
 #[derive(Debug, Clone)]
@@ -17,35 +38,38 @@ struct Rule {
 impl Rule {
     const UNCONDITIONAL: usize = 4;
 
-    fn new(rule: &str) -> Self {
-        if rule.contains(':') {
-            let mut parts = rule.split(':');
-            let condition = parts.next().unwrap();
-            let var_name = condition.as_bytes()[0];
-            let var_index = b"xmas".iter().position(|&c| c == var_name).unwrap();
-            let is_lower = condition.as_bytes()[1] == b'<';
-            let value: i32 = std::str::from_utf8(&condition.as_bytes()[2..])
-                .unwrap()
-                .parse()
-                .unwrap();
-            let action = parts.next().unwrap().to_string();
-            Self {
-                var_index,
-                is_lower,
-                value,
-                action,
-            }
-        } else {
-            let action = rule.as_bytes();
-            let action = std::str::from_utf8(&action[..action.len() - 1])
-                .unwrap()
-                .to_string();
-            Self {
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let condition = pair(
+            pair(identifier(), alt(literal("<"), literal(">"))),
+            terminated(integer(), literal(":")),
+        );
+        alt(
+            map(
+                pair(condition, identifier()),
+                |(((var_name, comparison), value), action)| Self {
+                    var_index: var_index(var_name),
+                    is_lower: comparison == "<",
+                    value,
+                    action: action.to_string(),
+                },
+            ),
+            map(identifier(), |action| Self {
                 var_index: Rule::UNCONDITIONAL,
                 is_lower: false,
                 value: 0,
-                action,
-            }
+                action: action.to_string(),
+            }),
+        )
+    }
+
+    /// The condition of the rule, e.g. "x<1416", or "else" if it is unconditional
+    fn condition(&self) -> String {
+        if self.var_index == Rule::UNCONDITIONAL {
+            "else".to_string()
+        } else {
+            let comparison = if self.is_lower { "<" } else { ">" };
+            let var_name = ["x", "m", "a", "s"][self.var_index];
+            format!("{var_name}{comparison}{}", self.value)
         }
     }
 
@@ -91,12 +115,16 @@ struct Workflow {
 }
 
 impl Workflow {
-    fn new(line: &str) -> Self {
-        let mut parts = line.split('{');
-        let name = parts.next().unwrap().to_string();
-        let rest = parts.next().unwrap();
-        let rules = rest.split(',').map(Rule::new).collect();
-        Self { name, rules }
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let rules = delimited(
+            literal("{"),
+            separated(Rule::parser(), literal(",")),
+            literal("}"),
+        );
+        map(pair(identifier(), rules), |(name, rules)| Self {
+            name: name.to_string(),
+            rules,
+        })
     }
 
     fn get_action(&self, part_rating: [i32; 4]) -> String {
@@ -194,31 +222,57 @@ impl Workflow {
     }
 }
 
-pub fn run(input: &str) {
-    let mut parts = input.split("\n\n");
-    let workflows: HashMap<String, Workflow> = parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let workflow = Workflow::new(line);
-            (workflow.name.clone(), workflow)
-        })
-        .collect();
-    let part_ratings: Vec<_> = parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(parse_part_rating)
+fn parse_system(input: &str) -> (HashMap<String, Workflow>, Vec<[i32; 4]>) {
+    let [workflows, part_ratings]: [_; 2] = blocks(input)
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("Invalid input: expected workflows, then part ratings");
+    let workflows = parse_all(separated(Workflow::parser(), literal("\n")), workflows)
+        .expect("Invalid workflows");
+    let part_ratings = parse_all(separated(part_rating_parser(), literal("\n")), part_ratings)
+        .expect("Invalid part ratings");
+    let workflows = workflows
+        .into_iter()
+        .map(|workflow| (workflow.name.clone(), workflow))
         .collect();
+    (workflows, part_ratings)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let (workflows, part_ratings) = parse_system(input);
 
     let total_accepted_value: i32 = part_ratings
         .iter()
         .filter(|&&part_rating| Workflow::is_accepted(&workflows, part_rating))
         .map(|part_rating| part_rating.iter().sum::<i32>())
         .sum();
-    println!("{}", total_accepted_value);
+    answers.push(total_accepted_value.to_string());
 
     let num_accepted_combinations = Workflow::count_accepted_combinations(&workflows);
-    println!("{}", num_accepted_combinations);
+    answers.push(num_accepted_combinations.to_string());
+    answers
+}
+
+/// The workflows, with an edge for every rule labelled by its condition
+pub fn to_dot(input: &str) -> Dot {
+    let (workflows, _) = parse_system(input);
+    let mut dot = Dot::directed();
+    dot.graph_attribute("rankdir", "LR");
+    dot.node_defaults(&[("shape", "box")]);
+    dot.node("in", &[("style", "bold")]);
+    dot.node("A", &[("shape", "doublecircle"), ("color", "green")]);
+    dot.node("R", &[("shape", "doublecircle"), ("color", "red")]);
+    // sorted by name, so that the output doesn't depend on the hash map's order
+    let workflows: BTreeMap<_, _> = workflows.into_iter().collect();
+    for workflow in workflows.values() {
+        for rule in &workflow.rules {
+            dot.edge(
+                &workflow.name,
+                &rule.action,
+                &[("label", &rule.condition())],
+            );
+        }
+    }
+    dot
 }
@@ -1,35 +1,43 @@
-use hashbrown::{HashMap, HashSet};
+use crate::utils::dot::Dot;
+use crate::utils::fast_hash::FxBuildHasher;
+use crate::utils::graph::Graph;
+use crate::utils::parse::{
+    alt, identifier, key_value, literal, map, parse_str, preceded, separated,
+};
+use hashbrown::HashMap;
 use std::collections::VecDeque;
+use std::hash::BuildHasher;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Module {
+#[derive(Debug, Clone)]
+enum Module<S> {
     FlipFlop(bool),
-    Conjunction(HashMap<String, bool>),
+    Conjunction(HashMap<String, bool, S>),
     Broadcaster,
 }
 
-fn parse_line(line: &str) -> (&str, (Module, Vec<&str>)) {
-    let mut parts = line.split(" -> ");
-    let prefix = parts.next().unwrap();
-    let module = match prefix.chars().next().unwrap() {
-        '%' => Module::FlipFlop(true),
-        '&' => Module::Conjunction(HashMap::new()),
-        'b' => Module::Broadcaster,
-        _ => unreachable!(),
-    };
-    let destinations: Vec<_> = parts.next().unwrap().split(", ").collect();
-    let name = if module == Module::Broadcaster {
-        prefix
-    } else {
-        std::str::from_utf8(&prefix.as_bytes()[1..]).unwrap()
-    };
+fn parse_line<S: BuildHasher + Default>(line: &str) -> (&str, (Module<S>, Vec<&str>)) {
+    let module = alt(
+        alt(
+            map(preceded(literal("%"), identifier()), |name| {
+                (name, Module::FlipFlop(true))
+            }),
+            map(preceded(literal("&"), identifier()), |name| {
+                (name, Module::Conjunction(HashMap::default()))
+            }),
+        ),
+        map(literal("broadcaster"), |name| (name, Module::Broadcaster)),
+    );
+    let destinations = separated(identifier(), literal(", "));
+    let ((name, module), destinations) =
+        parse_str(key_value(module, literal(" -> "), destinations), line)
+            .expect("Invalid line format");
     (name, (module, destinations))
 }
 
-fn push_button(
-    module_config: &mut HashMap<&str, (Module, Vec<&str>)>,
+fn push_button<S: BuildHasher + Default>(
+    module_config: &mut HashMap<&str, (Module<S>, Vec<&str>), S>,
     index: u64,
-    first_low_pulse: &mut HashMap<String, u64>,
+    first_low_pulse: &mut HashMap<String, u64, S>,
 ) -> (u32, u32) {
     let mut pulses = VecDeque::from([("button", "broadcaster", true)]);
     let mut num_low = 0;
@@ -74,36 +82,36 @@ fn push_button(
     (num_low, num_high)
 }
 
-pub fn run(input: &str) {
-    let mut module_inputs = HashMap::new();
-    let mut module_config: HashMap<_, _> = input
-        .lines()
-        .map(|line| {
-            let (name, (module, destinations)) = parse_line(line);
-            destinations.iter().for_each(|&destination| {
-                module_inputs
-                    .entry(destination)
-                    .and_modify(|set: &mut HashSet<_>| {
-                        set.insert(name);
-                    })
-                    .or_insert(HashSet::from([name]));
-            });
-            (name, (module, destinations))
-        })
-        .collect();
+/// The solver with another hasher for the maps, to compare them
+pub fn solve_with_hasher<S: BuildHasher + Default>(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let mut module_config: HashMap<_, _, S> = input.lines().map(parse_line).collect();
+    let wiring: Graph =
+        Graph::from_edges(module_config.iter().flat_map(|(&name, (_, destinations))| {
+            destinations
+                .iter()
+                .map(move |&destination| (name, destination, 1))
+        }));
+    let module_inputs = |name: &str| -> Vec<&str> {
+        wiring
+            .predecessors(wiring.index(name).unwrap())
+            .iter()
+            .map(|&(input, _)| wiring.name(input))
+            .collect()
+    };
     for (name, (module, _)) in module_config.iter_mut() {
         if let Module::Conjunction(ref mut state) = module {
-            for &input_name in module_inputs.get(name).unwrap() {
+            for input_name in module_inputs(name) {
                 state.insert(input_name.to_string(), true);
             }
         }
     }
-    let mut first_low_pulse = HashMap::new();
+    let mut first_low_pulse = HashMap::default();
     let (total_low, total_high) = (0..1000)
         .map(|index| push_button(&mut module_config, index, &mut first_low_pulse))
         .reduce(|(low1, high1), (low2, high2)| (low1 + low2, high1 + high2))
         .unwrap();
-    println!("{}", total_low * total_high);
+    answers.push((total_low * total_high).to_string());
 
     for index in 1000.. {
         push_button(&mut module_config, index, &mut first_low_pulse);
@@ -111,13 +119,33 @@ pub fn run(input: &str) {
             break;
         }
     }
-    let rx_inputs = module_inputs.get("rx").unwrap();
-    let &rx_input_name = rx_inputs.iter().next().unwrap();
-    let num_pushes_for_rx: u64 = module_inputs
-        .get(rx_input_name)
-        .unwrap()
+    let rx_input_name = module_inputs("rx")[0];
+    let num_pushes_for_rx: u64 = module_inputs(rx_input_name)
         .iter()
         .map(|&module_name| first_low_pulse.get(module_name).unwrap() + 1)
         .product();
-    println!("{}", num_pushes_for_rx);
+    answers.push(num_pushes_for_rx.to_string());
+    answers
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    solve_with_hasher::<FxBuildHasher>(input)
+}
+
+/// The module wiring: flip-flops are boxes, conjunctions are diamonds
+pub fn to_dot(input: &str) -> Dot {
+    let mut dot = Dot::directed();
+    for (name, (module, destinations)) in input.lines().map(parse_line::<FxBuildHasher>) {
+        let (label, shape) = match module {
+            Module::FlipFlop(_) => (format!("%{name}"), "box"),
+            Module::Conjunction(_) => (format!("&{name}"), "diamond"),
+            Module::Broadcaster => (name.to_string(), "doubleoctagon"),
+        };
+        dot.node(name, &[("label", &label), ("shape", shape)]);
+        for destination in destinations {
+            dot.edge(name, destination, &[]);
+        }
+    }
+    dot.node("rx", &[("shape", "doublecircle")]);
+    dot
 }
@@ -1,86 +1,119 @@
-use hashbrown::HashMap;
+use crate::utils::image::Image;
+use crate::utils::poly;
+use crate::utils::{BitGrid, Grid};
 
-use std::collections::VecDeque;
-
-fn find_start_plot(garden: &[&[u8]]) -> (i32, i32) {
+fn find_start_plot(garden: &[&[u8]]) -> (usize, usize) {
     for (y, row) in garden.iter().enumerate() {
         for (x, &c) in row.iter().enumerate() {
             if c == b'S' {
-                return (x as i32, y as i32);
+                return (x, y);
             }
         }
     }
     unreachable!()
 }
 
-fn get_visited_plots(
+/// Walks in the garden for max_steps steps, calling visit with the plots reachable after every
+/// step (and the plots of the garden, tiled if it is infinite)
+fn walk(
     garden: &[&[u8]],
-    num_steps: u64,
+    max_steps: usize,
     is_infinite: bool,
-) -> HashMap<(i32, i32), u64> {
-    let height = garden.len() as i32;
-    let width = garden[0].len() as i32;
+    mut visit: impl FnMut(u64, &BitGrid, &BitGrid),
+) {
+    let height = garden.len();
+    let width = garden[0].len();
+    // if the garden is infinite, tile enough copies so that the edges can never be reached
+    let num_copies = if is_infinite {
+        2 * max_steps.div_ceil(width.min(height)) + 1
+    } else {
+        1
+    };
+    let plots = BitGrid::from_fn(width * num_copies, height * num_copies, |x, y| {
+        garden[y % height][x % width] != b'#'
+    });
     let (start_x, start_y) = find_start_plot(garden);
-    let mut to_visit = VecDeque::from([(start_x, start_y, 0)]);
-    let mut visited = HashMap::new();
-    while !to_visit.is_empty() {
-        let (x, y, steps) = to_visit.pop_front().unwrap();
-        if !is_infinite && (x < 0 || x >= width || y < 0 || y >= height) {
-            continue;
-        }
-        let c = if is_infinite {
-            garden[y.rem_euclid(height) as usize][x.rem_euclid(width) as usize]
-        } else {
-            garden[y as usize][x as usize]
-        };
-        if c != b'.' && c != b'S' {
-            continue;
-        }
-        if visited.contains_key(&(x, y)) {
-            continue;
-        }
-        visited.insert((x, y), steps);
-        if steps < num_steps {
-            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                to_visit.push_back((x + dx, y + dy, steps + 1))
-            }
-        }
+    let mut reachable = BitGrid::new(plots.width(), plots.height());
+    reachable.set(
+        start_x + num_copies / 2 * width,
+        start_y + num_copies / 2 * height,
+        true,
+    );
+    for step in 0..=max_steps as u64 {
+        visit(step, &reachable, &plots);
+        reachable = reachable.neighbors();
+        reachable &= &plots;
     }
-    visited
 }
 
-fn count_reachable_plots(garden: &[&[u8]], num_steps: u64, is_infinite: bool) -> u64 {
-    get_visited_plots(garden, num_steps, is_infinite)
-        .values()
-        .filter(|&&v| v % 2 == if is_infinite { 1 } else { 0 })
-        .count() as u64
+/// Counts the plots reachable in exactly n steps, for every n in num_steps (sorted)
+fn count_reachable_plots(garden: &[&[u8]], num_steps: &[u64], is_infinite: bool) -> Vec<u64> {
+    let max_steps = *num_steps.last().unwrap() as usize;
+    let mut counts = vec![];
+    walk(garden, max_steps, is_infinite, |step, reachable, _| {
+        if num_steps.contains(&step) {
+            counts.push(reachable.count_ones() as u64);
+        }
+    });
+    counts
 }
 
-pub fn run(input: &str) {
+/// Counts the plots reachable in exactly part1_steps steps in the garden, and in exactly
+/// part2_steps steps in the infinite garden
+pub fn solve_with_steps(input: &str, part1_steps: u64, part2_steps: u64) -> Vec<String> {
+    let mut answers = vec![];
     let garden: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
-    let num_reachable_plots = count_reachable_plots(&garden, 64, false);
-    println!("{}", num_reachable_plots);
+    let num_reachable_plots = count_reachable_plots(&garden, &[part1_steps], false)[0];
+    answers.push(num_reachable_plots.to_string());
 
     let size = garden.len() as u64;
     assert!(garden[0].len() as u64 == size); // the garden must be a square
-    const NUM_STEPS: u64 = 26501365;
 
-    let num_visited: Vec<_> = (0..3)
-        .map(|n| {
-            let num_steps = 2 * size * n + NUM_STEPS % (2 * size);
-            count_reachable_plots(&garden, num_steps, true)
-        })
+    // The number of reachable plots grows quadratically every 2 * size steps
+    let num_steps: Vec<_> = (0..3)
+        .map(|n| 2 * size * n + part2_steps % (2 * size))
+        .collect();
+    let num_visited: Vec<_> = count_reachable_plots(&garden, &num_steps, true)
+        .into_iter()
+        .map(|count| count as i64)
         .collect();
+    let num_full_2x2_gardens = (part2_steps / (2 * size)) as i64;
+    let total_plots = poly::extrapolate(&num_visited, num_full_2x2_gardens);
+    answers.push(total_plots.to_string());
+    answers
+}
 
-    let max_reachable_per_2x2_garden = (num_visited[0] + num_visited[2] - 2 * num_visited[1]) / 4;
-    let num_full_2x2_gardens = NUM_STEPS / (2 * size);
-    let base_plots = max_reachable_per_2x2_garden - num_visited[0];
-    let extra_plots = 4 * max_reachable_per_2x2_garden + num_visited[0] - num_visited[1];
+pub fn solve(input: &str) -> Vec<String> {
+    solve_with_steps(input, 64, 26501365)
+}
 
-    let total_plots =
-        (2 * num_full_2x2_gardens * num_full_2x2_gardens + 2 * num_full_2x2_gardens + 1)
-            * max_reachable_per_2x2_garden
-            - base_plots
-            - extra_plots * num_full_2x2_gardens;
-    println!("{:?}", total_plots);
+/// The checkerboard of reachable plots, after the 64 steps of part 1, and in the infinite
+/// garden after the first steps of part 2
+pub fn render(input: &str) -> Vec<(String, Image)> {
+    let garden: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let size = garden.len();
+    [
+        ("64_steps", 64, false),
+        ("infinite", size / 2 + 2 * size, true),
+    ]
+    .into_iter()
+    .map(|(name, num_steps, is_infinite)| {
+        let mut image = None;
+        walk(&garden, num_steps, is_infinite, |step, reachable, plots| {
+            if step == num_steps as u64 {
+                let pattern = Grid::from_fn(plots.width(), plots.height(), |x, y| {
+                    if reachable.get(x, y) {
+                        [80, 200, 80]
+                    } else if plots.get(x, y) {
+                        [20, 60, 20]
+                    } else {
+                        [120, 110, 100]
+                    }
+                });
+                image = Some(Image::from_grid(&pattern, |&color| color));
+            }
+        });
+        (name.to_string(), image.unwrap())
+    })
+    .collect()
 }
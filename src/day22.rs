@@ -1,7 +1,7 @@
-use crate::utils::parse_ints;
+use crate::utils::parse_n;
 
 fn get_support_structure(
-    bricks_snapshot: &mut Vec<(i32, i32, i32, i32, i32, i32)>,
+    bricks_snapshot: &mut [(i32, i32, i32, i32, i32, i32)],
 ) -> (Vec<usize>, Vec<Vec<usize>>) {
     let num_bricks = bricks_snapshot.len();
     let mut num_supporting = vec![0; num_bricks];
@@ -71,11 +71,12 @@ fn count_falling_bricks(
     num_fallen - 1
 }
 
-pub fn run(input: &str) {
+pub fn solve(input: &str) -> Vec<String> {
+    let mut answers = vec![];
     let mut bricks_snapshot: Vec<_> = input
         .lines()
         .map(|line| {
-            let vals: Vec<i32> = parse_ints(line, false);
+            let vals: [i32; 6] = parse_n(line).unwrap();
             (vals[2], vals[5], vals[0], vals[3], vals[1], vals[4]) // z1-z2, x1-x2, y1-y2
         })
         .collect();
@@ -88,10 +89,11 @@ pub fn run(input: &str) {
         .enumerate()
         .filter(|&(_, supported)| supported.iter().all(|&index| num_supporting[index] != 1))
         .count();
-    println!("{}", num_safe_to_disintegrate);
+    answers.push(num_safe_to_disintegrate.to_string());
 
     let total_falling_bricks: u32 = (0..bricks_snapshot.len())
         .map(|index| count_falling_bricks(index, &num_supporting, &supported_bricks))
         .sum();
-    println!("{}", total_falling_bricks);
+    answers.push(total_falling_bricks.to_string());
+    answers
 }
@@ -1,8 +1,15 @@
+use crate::utils::dot::Dot;
+use crate::utils::fast_hash::FxBuildHasher;
+use crate::utils::viz::{Animation, Color, Scene, Style};
+use crate::utils::{FastSet, Grid};
 use hashbrown::HashMap;
 
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
+use std::hash::BuildHasher;
 
-type Graph = HashMap<(i32, i32), Vec<((i32, i32), u32)>>;
+type Graph<S = FxBuildHasher> = HashMap<(i32, i32), Vec<((i32, i32), u32)>, S>;
+/// Crossroads, each with the distance from the previous one
+type Path = Vec<((i32, i32), u32)>;
 
 fn successors(pos: (i32, i32), grid: &[Vec<u8>], is_slippery: bool) -> Vec<((i32, i32), u32)> {
     let width = grid[0].len() as i32;
@@ -46,8 +53,13 @@ fn successors(pos: (i32, i32), grid: &[Vec<u8>], is_slippery: bool) -> Vec<((i32
         .collect()
 }
 
-fn grid_to_graph(grid: &[Vec<u8>], start: (i32, i32), end: (i32, i32), is_slippery: bool) -> Graph {
-    let mut graph = HashMap::new();
+fn grid_to_graph<S: BuildHasher + Default>(
+    grid: &[Vec<u8>],
+    start: (i32, i32),
+    end: (i32, i32),
+    is_slippery: bool,
+) -> Graph<S> {
+    let mut graph = Graph::default();
     let mut to_visit = VecDeque::from([start]);
     while let Some(pos) = to_visit.pop_front() {
         let next = if pos == end {
@@ -65,8 +77,10 @@ fn grid_to_graph(grid: &[Vec<u8>], start: (i32, i32), end: (i32, i32), is_slippe
     graph
 }
 
-fn keep_only_longest_to_same_destination(next: &[((i32, i32), u32)]) -> Vec<((i32, i32), u32)> {
-    let mut longest: HashMap<(i32, i32), u32> = HashMap::new();
+fn keep_only_longest_to_same_destination<S: BuildHasher + Default>(
+    next: &[((i32, i32), u32)],
+) -> Vec<((i32, i32), u32)> {
+    let mut longest: HashMap<(i32, i32), u32, S> = HashMap::default();
     next.iter().for_each(|&(pos, distance)| {
         longest
             .entry(pos)
@@ -80,8 +94,12 @@ fn keep_only_longest_to_same_destination(next: &[((i32, i32), u32)]) -> Vec<((i3
     longest.into_iter().collect()
 }
 
-fn graph_simplification_step(graph: &Graph, start: (i32, i32), end: (i32, i32)) -> Graph {
-    let mut simpler_graph = HashMap::new();
+fn graph_simplification_step<S: BuildHasher + Default>(
+    graph: &Graph<S>,
+    start: (i32, i32),
+    end: (i32, i32),
+) -> Graph<S> {
+    let mut simpler_graph = Graph::default();
     let mut to_visit = VecDeque::from([start]);
     while let Some(crossroad_pos) = to_visit.pop_front() {
         if simpler_graph.contains_key(&crossroad_pos) {
@@ -119,7 +137,7 @@ fn graph_simplification_step(graph: &Graph, start: (i32, i32), end: (i32, i32))
         }
         simpler_graph.insert(
             crossroad_pos,
-            keep_only_longest_to_same_destination(&next_crossroads),
+            keep_only_longest_to_same_destination::<S>(&next_crossroads),
         );
         next_crossroads
             .into_iter()
@@ -129,7 +147,11 @@ fn graph_simplification_step(graph: &Graph, start: (i32, i32), end: (i32, i32))
     simpler_graph
 }
 
-fn simplify_graph(graph: &Graph, start: (i32, i32), end: (i32, i32)) -> Graph {
+fn simplify_graph<S: BuildHasher + Default>(
+    graph: &Graph<S>,
+    start: (i32, i32),
+    end: (i32, i32),
+) -> Graph<S> {
     let mut graph_size = graph.len();
     let mut simpler_graph = graph_simplification_step(graph, start, end);
     while simpler_graph.len() < graph_size {
@@ -139,9 +161,15 @@ fn simplify_graph(graph: &Graph, start: (i32, i32), end: (i32, i32)) -> Graph {
     simpler_graph
 }
 
-fn find_longest_path(graph: &Graph, start: (i32, i32), end: (i32, i32)) -> Option<u32> {
+/// The length of the longest path, with the crossroads along it (the start and end included),
+/// each with the distance from the previous one
+fn find_longest_path<S: BuildHasher + Default>(
+    graph: &Graph<S>,
+    start: (i32, i32),
+    end: (i32, i32),
+) -> Option<(u32, Path)> {
     if start == end {
-        return Some(0);
+        return Some((0, vec![(end, 0)]));
     }
     let mut graph = simplify_graph(graph, start, end);
 
@@ -149,17 +177,75 @@ fn find_longest_path(graph: &Graph, start: (i32, i32), end: (i32, i32)) -> Optio
     if let Some(next) = next {
         next.iter()
             .filter_map(|&(next_pos, distance)| {
-                find_longest_path(&graph, next_pos, end)
-                    .map(|longest_remaining| longest_remaining + distance)
+                find_longest_path(&graph, next_pos, end).map(|(longest_remaining, mut path)| {
+                    path[0].1 = distance;
+                    (longest_remaining + distance, path)
+                })
+            })
+            .max_by_key(|&(length, _)| length)
+            .map(|(length, mut path)| {
+                path.insert(0, (start, 0));
+                (length, path)
             })
-            .max()
     } else {
         None
     }
 }
 
-pub fn run(input: &str) {
-    let grid: Vec<_> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+/// Finds the cells of trails through the crossroads of the path, with the right lengths, around
+/// the cells already visited. Several trails can have the same length, so a choice that blocks
+/// the next trails is undone.
+fn find_trails(
+    graph: &Graph,
+    pos: (i32, i32),
+    path: &[((i32, i32), u32)],
+    length: u32,
+    visited: &mut FastSet<(i32, i32)>,
+    cells: &mut Vec<(i32, i32)>,
+) -> bool {
+    let Some(&(to, _)) = path.first() else {
+        return true;
+    };
+    if pos == to || length == 0 {
+        return pos == to
+            && length == 0
+            && path.get(1).is_none_or(|&(_, distance)| {
+                find_trails(graph, pos, &path[1..], distance, visited, cells)
+            });
+    }
+    for &(next_pos, _) in graph.get(&pos).unwrap_or(&vec![]) {
+        if visited.insert(next_pos) {
+            cells.push(next_pos);
+            if find_trails(graph, next_pos, path, length - 1, visited, cells) {
+                return true;
+            }
+            cells.pop();
+            visited.remove(&next_pos);
+        }
+    }
+    false
+}
+
+/// The cells of the path through the crossroads, in the graph of the cells
+fn path_cells(graph: &Graph, path: &Path) -> Vec<(i32, i32)> {
+    let (start, _) = path[0];
+    let mut cells = vec![start];
+    let mut visited = FastSet::from_iter([start]);
+    if path.len() > 1 {
+        let is_found = find_trails(
+            graph,
+            start,
+            &path[1..],
+            path[1].1,
+            &mut visited,
+            &mut cells,
+        );
+        assert!(is_found, "No trails through the crossroads of the path");
+    }
+    cells
+}
+
+fn find_start_and_end(grid: &[Vec<u8>]) -> ((i32, i32), (i32, i32)) {
     let start = (grid[0].iter().position(|&c| c == b'.').unwrap() as i32, 0);
     let end = (
         grid.last()
@@ -169,9 +255,80 @@ pub fn run(input: &str) {
             .unwrap() as i32,
         (grid.len() - 1) as i32,
     );
+    (start, end)
+}
+
+/// The solver with another hasher for the graphs, to compare them
+pub fn solve_with_hasher<S: BuildHasher + Default>(input: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let grid: Vec<_> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+    let (start, end) = find_start_and_end(&grid);
     for is_slippery in [true, false] {
-        let graph = grid_to_graph(&grid, start, end, is_slippery);
-        let max_steps = find_longest_path(&graph, start, end).unwrap();
-        println!("{}", max_steps);
+        let graph = grid_to_graph::<S>(&grid, start, end, is_slippery);
+        let (max_steps, _) = find_longest_path(&graph, start, end).unwrap();
+        answers.push(max_steps.to_string());
+    }
+    answers
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    solve_with_hasher::<FxBuildHasher>(input)
+}
+
+/// Draws the longest hike on slippery slopes, then the one on dry slopes, as they progress
+pub fn visualize(input: &str) -> Animation {
+    let rows: Vec<_> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+    let grid = Grid::parse(input);
+    let (start, end) = find_start_and_end(&rows);
+    let mut animation = Animation::new();
+    for (is_slippery, color) in [(true, Color::Green), (false, Color::Magenta)] {
+        let graph: Graph = grid_to_graph(&rows, start, end, is_slippery);
+        let crossroads: Vec<_> = simplify_graph(&graph, start, end)
+            .into_keys()
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect();
+        let (length, path) = find_longest_path(&graph, start, end).unwrap();
+        let cells: Vec<_> = path_cells(&graph, &path)
+            .into_iter()
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect();
+        debug_assert_eq!(cells.len(), length as usize + 1);
+        let step = (cells.len() / 50).max(1);
+        for length in (1..cells.len()).step_by(step).chain([cells.len()]) {
+            let scene = Scene::new(&grid, |&c| if c == b'#' { '█' } else { c as char })
+                .overlay(
+                    crossroads.clone(),
+                    Style::foreground(Color::Yellow).with_symbol('+'),
+                )
+                .overlay(cells[..length].to_vec(), Style::background(color));
+            animation.record(&scene);
+        }
+    }
+    animation
+}
+
+/// The trails compressed into a graph of crossroads, labelled with the trail lengths
+pub fn to_dot(input: &str) -> Dot {
+    let grid: Vec<_> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
+    let (start, end) = find_start_and_end(&grid);
+    let graph: Graph = simplify_graph(&grid_to_graph(&grid, start, end, false), start, end);
+    let name = |(x, y): (i32, i32)| format!("{x},{y}");
+    let mut dot = Dot::undirected();
+    dot.node(
+        &name(start),
+        &[("shape", "doublecircle"), ("color", "green")],
+    );
+    dot.node(&name(end), &[("shape", "doublecircle"), ("color", "red")]);
+    // the trails are in the graph once or in both directions
+    let trails: BTreeSet<_> = graph
+        .iter()
+        .flat_map(|(&from, next)| {
+            next.iter()
+                .map(move |&(to, distance)| (from.min(to), from.max(to), distance))
+        })
+        .collect();
+    for (from, to, distance) in trails {
+        dot.edge(&name(from), &name(to), &[("label", &distance.to_string())]);
     }
+    dot
 }
@@ -0,0 +1,878 @@
+//! Generators of synthetic puzzle inputs, with the same format and structure as the real ones
+//! (so that the solvers' assumptions hold), to share examples or stress-test the solvers.
+
+use crate::utils::rng::Rng;
+use crate::utils::{FastMap, FastSet, Grid, UnionFind};
+
+use itertools::Itertools;
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Generates an input for the day, the size being the number of lines, the side of the grid
+/// or whatever makes the input bigger. Returns None if there is no such day.
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<String> {
+    let generator = match day {
+        1 => day01,
+        2 => day02,
+        3 => day03,
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07,
+        8 => day08,
+        9 => day09,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        16 => day16,
+        17 => day17,
+        18 => day18,
+        19 => day19,
+        20 => day20,
+        21 => day21,
+        22 => day22,
+        23 => day23,
+        24 => day24,
+        25 => day25,
+        _ => return None,
+    };
+    let mut rng = Rng::new(seed);
+    Some(generator(&mut rng, size.max(1)))
+}
+
+fn random_word(rng: &mut Rng, alphabet: &[u8], length: usize) -> String {
+    (0..length).map(|_| *rng.choose(alphabet) as char).collect()
+}
+
+/// Distinct random names, all accepted by is_valid
+fn unique_names(
+    rng: &mut Rng,
+    count: usize,
+    alphabet: &[u8],
+    length: usize,
+    is_valid: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    assert!(
+        (count as f64) < (alphabet.len() as f64).powi(length as i32) / 2.0,
+        "Size too large"
+    );
+    let mut names = FastSet::default();
+    let mut ordered_names = vec![];
+    while ordered_names.len() < count {
+        let name = random_word(rng, alphabet, length);
+        if is_valid(&name) && names.insert(name.clone()) {
+            ordered_names.push(name);
+        }
+    }
+    ordered_names
+}
+
+fn random_grid(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> u8) -> String {
+    (0..height)
+        .map(|y| (0..width).map(|x| cell(x, y) as char).collect::<String>())
+        .join("\n")
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+/// A random simple closed loop on the integer grid, made of unit steps: the outline of the
+/// cells used by a random spanning tree of a k x k lattice, drawn with corridors of width 1.
+/// Its points are in 0..=2k-1 and it starts at (0, 0).
+fn random_loop(rng: &mut Rng, k: usize) -> Vec<(i64, i64)> {
+    let n = 2 * k - 1;
+    let mut is_used = Grid::from_fn(n, n, |x, y| x % 2 == 0 && y % 2 == 0);
+    let mut edges: Vec<_> = (0..k)
+        .cartesian_product(0..k)
+        .flat_map(|(i, j)| [((i, j), (i + 1, j)), ((i, j), (i, j + 1))])
+        .filter(|&(_, (i, j))| i < k && j < k)
+        .collect();
+    rng.shuffle(&mut edges);
+    let mut tree = UnionFind::new(k * k);
+    for ((i1, j1), (i2, j2)) in edges {
+        if tree.union(j1 * k + i1, j2 * k + i2) {
+            is_used.set(i1 + i2, j1 + j2, true);
+        }
+    }
+    // the edges of the outline separate a used cell from an unused one
+    let is_used_at = |x: i64, y: i64| is_used.try_get(x, y).copied().unwrap_or(false);
+    let neighbors = |(x, y): (i64, i64)| {
+        [
+            (is_used_at(x, y - 1) != is_used_at(x, y), (x + 1, y)),
+            (is_used_at(x - 1, y - 1) != is_used_at(x - 1, y), (x - 1, y)),
+            (is_used_at(x - 1, y) != is_used_at(x, y), (x, y + 1)),
+            (is_used_at(x - 1, y - 1) != is_used_at(x, y - 1), (x, y - 1)),
+        ]
+        .into_iter()
+        .filter(|&(is_edge, _)| is_edge)
+        .map(|(_, point)| point)
+        .collect_vec()
+    };
+    let start = (0, 0);
+    let mut points = vec![start];
+    let (mut previous, mut current) = (start, neighbors(start)[0]);
+    while current != start {
+        points.push(current);
+        let next = neighbors(current)
+            .into_iter()
+            .find(|&point| point != previous)
+            .unwrap();
+        (previous, current) = (current, next);
+    }
+    points
+}
+
+fn day01(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut pieces: Vec<String> = (0..rng.below(6) + 1)
+                .map(|_| match rng.below(3) {
+                    0 => rng.below(10).to_string(),
+                    1 => rng.choose(&DIGIT_NAMES).to_string(),
+                    _ => {
+                        let length = rng.below(5) + 1;
+                        random_word(rng, LOWERCASE, length)
+                    }
+                })
+                .collect();
+            if !pieces.concat().bytes().any(|c| c.is_ascii_digit()) {
+                let index = rng.below(pieces.len() + 1);
+                pieces.insert(index, (rng.below(9) + 1).to_string());
+            }
+            pieces.concat()
+        })
+        .join("\n")
+}
+
+fn day02(rng: &mut Rng, size: usize) -> String {
+    (1..=size)
+        .map(|game_id| {
+            let draws = (0..rng.below(6) + 1)
+                .map(|_| {
+                    let mut colors = vec!["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    colors[..rng.below(3) + 1]
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.below(20) + 1))
+                        .join(", ")
+                })
+                .join("; ");
+            format!("Game {game_id}: {draws}")
+        })
+        .join("\n")
+}
+
+fn day03(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(4);
+    (0..size)
+        .map(|_| {
+            let mut row = String::new();
+            while row.len() < width {
+                if rng.chance(0.15) && width - row.len() >= 4 {
+                    row += &format!("{}.", rng.below(999) + 1);
+                } else if rng.chance(0.1) {
+                    row.push(*rng.choose(b"*#+$/@%&=-") as char);
+                } else {
+                    row.push('.');
+                }
+            }
+            row.truncate(width);
+            row
+        })
+        .join("\n")
+}
+
+fn day04(rng: &mut Rng, size: usize) -> String {
+    (1..=size)
+        .map(|card_id| {
+            let mut numbers: Vec<u32> = (1..100).collect();
+            rng.shuffle(&mut numbers);
+            let num_matching = if rng.chance(0.5) {
+                0
+            } else {
+                rng.below(10) + 1
+            };
+            let mut winning_numbers = numbers[..10].to_vec();
+            let mut our_numbers = numbers[..num_matching].to_vec();
+            our_numbers.extend(&numbers[10..35 - num_matching]);
+            // sometimes a number is repeated, on either side
+            for numbers in [&mut winning_numbers, &mut our_numbers] {
+                if rng.chance(0.2) {
+                    numbers[0] = *rng.choose(&numbers[1..]);
+                }
+            }
+            rng.shuffle(&mut winning_numbers);
+            rng.shuffle(&mut our_numbers);
+            let format_numbers =
+                |numbers: &[u32]| numbers.iter().map(|n| format!("{n:2}")).join(" ");
+            format!(
+                "Card {card_id:3}: {} | {}",
+                format_numbers(&winning_numbers),
+                format_numbers(&our_numbers)
+            )
+        })
+        .join("\n")
+}
+
+fn day05(rng: &mut Rng, size: usize) -> String {
+    // small enough for the reference solver to try every seed at small sizes
+    let max_value = (size as i64).pow(3).saturating_mul(100).min(1 << 32);
+    // some seeds go past the mapped values, where every map is the identity
+    let seeds = (0..5)
+        .map(|_| {
+            let length = rng.range(1..=max_value / 20);
+            format!("{} {length}", rng.range(0..=max_value * 5 / 4 - length))
+        })
+        .join(" ");
+    let categories = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    // a quarter of the almanacs keep all their maps, so that the seed-to-location map is a
+    // bijection
+    let drop_probability = if rng.chance(0.25) { 0.0 } else { 0.2 };
+    let maps = categories
+        .iter()
+        .tuple_windows()
+        .map(|(source, destination)| {
+            // cut the values into ranges, and send them to a shuffled layout of these ranges,
+            // leaving some of them out (so they map to themselves), possibly all of them
+            let mut bounds: Vec<_> = (0..size).map(|_| rng.range(1..=max_value - 1)).collect();
+            bounds.extend([0, max_value]);
+            bounds.sort();
+            bounds.dedup();
+            let mut ranges: Vec<_> = bounds.windows(2).map(|pair| (pair[0], pair[1])).collect();
+            rng.shuffle(&mut ranges);
+            let mut destination_start = 0;
+            let mut lines = vec![];
+            for (start, end) in ranges {
+                if !rng.chance(drop_probability) {
+                    lines.push(format!("\n{destination_start} {start} {}", end - start));
+                }
+                destination_start += end - start;
+            }
+            rng.shuffle(&mut lines);
+            format!("{source}-to-{destination} map:{}", lines.concat())
+        })
+        .join("\n\n");
+    format!("seeds: {seeds}\n\n{maps}")
+}
+
+fn day06(rng: &mut Rng, size: usize) -> String {
+    let races: Vec<_> = (0..size.min(4))
+        .map(|_| {
+            let time = rng.range(7..=99);
+            let best_distance = (time / 2) * (time - time / 2);
+            (time, rng.range(0..=best_distance - 1))
+        })
+        .collect();
+    let times = races.iter().map(|(time, _)| format!("{time:4}")).join(" ");
+    let distances = races
+        .iter()
+        .map(|(_, distance)| format!("{distance:4}"))
+        .join(" ");
+    format!("Time:     {times}\nDistance: {distances}")
+}
+
+fn day07(rng: &mut Rng, size: usize) -> String {
+    let hands = unique_names(rng, size, b"23456789TJQKA", 5, |_| true);
+    hands
+        .iter()
+        .map(|hand| format!("{hand} {}", rng.below(1000) + 1))
+        .join("\n")
+}
+
+fn day08(rng: &mut Rng, size: usize) -> String {
+    // Every ghost walks a cycle of period m * L, where L is the length of the directions and m
+    // is a prime, and ends it on its Z node. Since the period is a multiple of L, every node
+    // is always left in the same direction, the other direction leads to a decoy.
+    let directions = random_word(rng, b"LR", size);
+    let mut periods = vec![2, 3, 5, 7, 11, 13, 17, 19];
+    rng.shuffle(&mut periods);
+    let num_ghosts = rng.below(4) + 1;
+    let num_nodes: usize = periods[..num_ghosts].iter().map(|m| m * size).sum();
+    let mut names = unique_names(rng, num_nodes, UPPERCASE, 3, |name| {
+        !name.ends_with('A') && !name.ends_with('Z')
+    })
+    .into_iter();
+    let mut ends = unique_names(rng, num_ghosts, UPPERCASE, 2, |name| {
+        name != "AA" && name != "ZZ"
+    })
+    .into_iter();
+    let mut lines = vec![];
+    for (ghost, &period) in periods[..num_ghosts].iter().enumerate() {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            let prefix = ends.next().unwrap();
+            (format!("{prefix}A"), format!("{prefix}Z"))
+        };
+        let mut cycle: Vec<_> = names.by_ref().take(period * size - 1).collect();
+        cycle.push(end);
+        for (step, node) in [&start].into_iter().chain(&cycle).enumerate() {
+            let next = &cycle[step % cycle.len()];
+            let decoy = rng.choose(&cycle);
+            let (left, right) = if directions.as_bytes()[step % size] == b'L' {
+                (next, decoy)
+            } else {
+                (decoy, next)
+            };
+            lines.push(format!("{node} = ({left}, {right})"));
+        }
+    }
+    rng.shuffle(&mut lines);
+    format!("{directions}\n\n{}", lines.join("\n"))
+}
+
+fn day09(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let coefficients: Vec<_> = (0..rng.below(6) + 2).map(|_| rng.range(-5..=5)).collect();
+            (0..21)
+                .map(|x: i64| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, coefficient| value * x + coefficient)
+                })
+                .join(" ")
+        })
+        .join("\n")
+}
+
+fn pipe_between(a: (i64, i64), b: (i64, i64), c: (i64, i64)) -> u8 {
+    let directions = [(a.0 - b.0, a.1 - b.1), (c.0 - b.0, c.1 - b.1)];
+    let has = |direction| directions.contains(&direction);
+    match (has((0, -1)), has((0, 1)), has((-1, 0)), has((1, 0))) {
+        (true, true, _, _) => b'|',
+        (_, _, true, true) => b'-',
+        (true, _, true, _) => b'J',
+        (true, _, _, true) => b'L',
+        (_, true, true, _) => b'7',
+        _ => b'F',
+    }
+}
+
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let k = (size / 2).max(2);
+    let main_loop = random_loop(rng, k);
+    let mut grid = Grid::from_fn(2 * k + 2, 2 * k + 2, |_, _| *rng.choose(b"|-LJ7F..."));
+    for (index, &point) in main_loop.iter().enumerate() {
+        let previous = main_loop[(index + main_loop.len() - 1) % main_loop.len()];
+        let next = main_loop[(index + 1) % main_loop.len()];
+        let pipe = pipe_between(previous, point, next);
+        grid.set(point.0 as usize + 1, point.1 as usize + 1, pipe);
+    }
+    let start = *rng.choose(&main_loop);
+    let (start_x, start_y) = (start.0 as usize + 1, start.1 as usize + 1);
+    grid.set(start_x, start_y, b'S');
+    // pipes that are not on the loop must not connect to S, so that S has a single loop
+    for (dx, dy, connecting) in [
+        (1, 0, b"-J7"),
+        (-1, 0, b"-LF"),
+        (0, 1, b"|LJ"),
+        (0, -1, b"|7F"),
+    ] {
+        let (x, y) = (start_x as i64 + dx, start_y as i64 + dy);
+        let point = (x - 1, y - 1);
+        let is_on_loop = main_loop.contains(&point);
+        if !is_on_loop && grid.try_get(x, y).is_some_and(|c| connecting.contains(c)) {
+            grid.set(x as usize, y as usize, b'.');
+        }
+    }
+    random_grid(grid.width(), grid.height(), |x, y| *grid.get(x, y))
+}
+
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut galaxies = Grid::from_fn(size, size, |_, _| rng.chance(0.03));
+    galaxies.set(rng.below(size), 0, true);
+    galaxies.set(rng.below(size), size - 1, true);
+    random_grid(
+        size,
+        size,
+        |x, y| {
+            if *galaxies.get(x, y) {
+                b'#'
+            } else {
+                b'.'
+            }
+        },
+    )
+}
+
+fn day12(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let length = rng.below(16) + 5;
+            let mut springs: Vec<u8> = (0..length)
+                .map(|_| if rng.chance(0.5) { b'#' } else { b'.' })
+                .collect();
+            if !springs.contains(&b'#') {
+                springs[rng.below(length)] = b'#';
+            }
+            let groups = springs
+                .split(|&c| c == b'.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len())
+                .join(",");
+            let record: String = springs
+                .iter()
+                .map(|&c| if rng.chance(0.4) { '?' } else { c as char })
+                .collect();
+            format!("{record} {groups}")
+        })
+        .join("\n")
+}
+
+/// The number of mismatched cells for every vertical then horizontal reflection line
+fn reflection_errors(pattern: &Grid<bool>) -> Vec<usize> {
+    let (width, height) = (pattern.width(), pattern.height());
+    let vertical = (1..width).map(|x| {
+        (0..height)
+            .cartesian_product(0..x.min(width - x))
+            .filter(|&(y, offset)| pattern.get(x - 1 - offset, y) != pattern.get(x + offset, y))
+            .count()
+    });
+    let horizontal = (1..height).map(|y| {
+        (0..width)
+            .cartesian_product(0..y.min(height - y))
+            .filter(|&(x, offset)| pattern.get(x, y - 1 - offset) != pattern.get(x, y + offset))
+            .count()
+    });
+    vertical.chain(horizontal).collect()
+}
+
+fn day13(rng: &mut Rng, size: usize) -> String {
+    // Every pattern is symmetric around a column a and a row b, both off-center, then a cell in
+    // a column that column a does not reflect is flipped: this is the smudge for row b.
+    (0..size)
+        .map(|_| {
+            let width = rng.below(13) + 5;
+            let height = rng.below(13) + 5;
+            let a = rng.below((width - 3) / 2 + 1);
+            let b = rng.below((height - 3) / 2 + 1);
+            let fold = |i: usize, mirror: usize| {
+                if i <= 2 * mirror + 1 {
+                    i.min(2 * mirror + 1 - i)
+                } else {
+                    i
+                }
+            };
+            // retry until no other line is perfect or has a single error by chance
+            let pattern = loop {
+                let base = Grid::from_fn(width, height, |_, _| rng.chance(0.5));
+                let mut pattern =
+                    Grid::from_fn(width, height, |x, y| *base.get(fold(x, a), fold(y, b)));
+                let smudge_x = 2 * a + 2 + rng.below(width - 2 * a - 2);
+                let smudge_y = rng.below(2 * b + 2);
+                let is_rock = *pattern.get(smudge_x, smudge_y);
+                pattern.set(smudge_x, smudge_y, !is_rock);
+                let errors = reflection_errors(&pattern);
+                if errors.iter().filter(|&&n| n == 0).count() == 1
+                    && errors.iter().filter(|&&n| n == 1).count() == 1
+                {
+                    break pattern;
+                }
+            };
+            random_grid(
+                width,
+                height,
+                |x, y| {
+                    if *pattern.get(x, y) {
+                        b'#'
+                    } else {
+                        b'.'
+                    }
+                },
+            )
+        })
+        .join("\n\n")
+}
+
+fn day14(rng: &mut Rng, size: usize) -> String {
+    random_grid(size, size, |_, _| match rng.below(20) {
+        0..=2 => b'O',
+        3..=4 => b'#',
+        _ => b'.',
+    })
+}
+
+fn day15(rng: &mut Rng, size: usize) -> String {
+    let labels: Vec<_> = (0..size / 3 + 1)
+        .map(|_| {
+            let length = rng.below(5) + 2;
+            random_word(rng, LOWERCASE, length)
+        })
+        .collect();
+    (0..size)
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(0.3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.below(9) + 1)
+            }
+        })
+        .join(",")
+}
+
+fn day16(rng: &mut Rng, size: usize) -> String {
+    random_grid(size, size, |_, _| {
+        if rng.chance(0.1) {
+            *rng.choose(b"|-\\/")
+        } else {
+            b'.'
+        }
+    })
+}
+
+fn day17(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+    random_grid(size, size, |_, _| b'1' + rng.below(9) as u8)
+}
+
+/// Random increasing coordinates, with gaps in 2..=max_gap so that parallel trenches never touch
+fn random_coordinates(rng: &mut Rng, count: usize, max_gap: i64) -> Vec<i64> {
+    (0..count)
+        .scan(0, |coordinate, index| {
+            if index > 0 {
+                *coordinate += rng.range(2..=max_gap);
+            }
+            Some(*coordinate)
+        })
+        .collect()
+}
+
+fn day18(rng: &mut Rng, size: usize) -> String {
+    // the trench outlines the same random loop in both parts, stretched differently
+    let k = (size / 2).max(2);
+    let main_loop = random_loop(rng, k);
+    let corners: Vec<_> = main_loop
+        .iter()
+        .enumerate()
+        .filter(|&(index, &(x, y))| {
+            let (previous_x, previous_y) =
+                main_loop[(index + main_loop.len() - 1) % main_loop.len()];
+            let (next_x, next_y) = main_loop[(index + 1) % main_loop.len()];
+            (x - previous_x, y - previous_y) != (next_x - x, next_y - y)
+        })
+        .map(|(_, &point)| point)
+        .collect();
+    let num_coordinates = 2 * k;
+    let (x1, y1) = (
+        random_coordinates(rng, num_coordinates, 6),
+        random_coordinates(rng, num_coordinates, 6),
+    );
+    let max_gap = 0xfffff / num_coordinates as i64;
+    let (x2, y2) = (
+        random_coordinates(rng, num_coordinates, max_gap),
+        random_coordinates(rng, num_coordinates, max_gap),
+    );
+    corners
+        .iter()
+        .circular_tuple_windows()
+        .map(|(&(xa, ya), &(xb, yb))| {
+            let (direction, digit) = match ((xb - xa).signum(), (yb - ya).signum()) {
+                (1, 0) => ("R", 0),
+                (0, 1) => ("D", 1),
+                (-1, 0) => ("L", 2),
+                _ => ("U", 3),
+            };
+            let [xa, xb, ya, yb] = [xa, xb, ya, yb].map(|coordinate| coordinate as usize);
+            let length1 = (x1[xb] - x1[xa]).abs() + (y1[yb] - y1[ya]).abs();
+            let length2 = (x2[xb] - x2[xa]).abs() + (y2[yb] - y2[ya]).abs();
+            let color = format!("#{length2:05x}{digit}");
+            format!("{direction} {length1} ({color})")
+        })
+        .join("\n")
+}
+
+fn day19(rng: &mut Rng, size: usize) -> String {
+    // the workflows form a tree rooted at "in", which makes them solvable
+    let names: Vec<_> = ["in".to_string()]
+        .into_iter()
+        .chain(unique_names(rng, size - 1, LOWERCASE, 3, |name| {
+            name != "in"
+        }))
+        .collect();
+    let mut actions = vec![vec![]; size];
+    for (index, name) in names.iter().enumerate().skip(1) {
+        actions[rng.below(index)].push(name.clone());
+    }
+    let workflows = names
+        .iter()
+        .zip(actions)
+        .map(|(name, mut actions)| {
+            actions.extend((0..rng.below(2) + 1).map(|_| rng.choose(&["A", "R"]).to_string()));
+            rng.shuffle(&mut actions);
+            let last_action = actions.pop().unwrap();
+            let rules = actions
+                .iter()
+                .map(|action| {
+                    let variable = *rng.choose(b"xmas") as char;
+                    let comparison = rng.choose(&["<", ">"]);
+                    format!("{variable}{comparison}{}:{action}", rng.below(3999) + 1)
+                })
+                .chain([last_action])
+                .join(",");
+            format!("{name}{{{rules}}}")
+        })
+        .join("\n");
+    let parts = (0..size)
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.below(4000) + 1);
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .join("\n");
+    format!("{workflows}\n\n{parts}")
+}
+
+fn day20(rng: &mut Rng, size: usize) -> String {
+    // The broadcaster feeds several binary counters made of flip-flops. Each counter resets
+    // itself when it reaches a prime number, and then sends a pulse through an inverter to the
+    // final conjunction in front of rx.
+    let num_bits = size.clamp(2, 16);
+    // distinct primes, like in the real inputs, so that the cycle lengths are coprime
+    let mut primes: Vec<_> = (1u64 << (num_bits - 1)..1 << num_bits)
+        .filter(|&n| n > 2 && is_prime(n))
+        .collect();
+    rng.shuffle(&mut primes);
+    let num_counters = primes.len().min(4);
+    let num_modules = num_counters * (num_bits + 2) + 1;
+    let mut names = unique_names(rng, num_modules, LOWERCASE, 2, |name| name != "rx").into_iter();
+    let final_conjunction = names.next().unwrap();
+    let mut lines = vec![format!("&{final_conjunction} -> rx")];
+    let mut counter_starts = vec![];
+    for &prime in &primes[..num_counters] {
+        let flip_flops: Vec<_> = names.by_ref().take(num_bits).collect();
+        let conjunction = names.next().unwrap();
+        let inverter = names.next().unwrap();
+        let mut conjunction_outputs = vec![flip_flops[0].clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs = vec![];
+            if let Some(next) = flip_flops.get(bit + 1) {
+                outputs.push(next.clone());
+            }
+            if (prime >> bit) & 1 == 1 {
+                outputs.push(conjunction.clone());
+            } else {
+                conjunction_outputs.push(flip_flop.clone());
+            }
+            rng.shuffle(&mut outputs);
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+        conjunction_outputs.push(inverter.clone());
+        rng.shuffle(&mut conjunction_outputs);
+        lines.push(format!(
+            "&{conjunction} -> {}",
+            conjunction_outputs.join(", ")
+        ));
+        lines.push(format!("&{inverter} -> {final_conjunction}"));
+        counter_starts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", counter_starts.join(", ")));
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+fn day21(rng: &mut Rng, size: usize) -> String {
+    // like the real gardens: odd size, S in the center, clear middle row, column and border
+    let size = size.max(5) | 1;
+    let center = size / 2;
+    random_grid(size, size, |x, y| {
+        if x == center && y == center {
+            b'S'
+        } else if x == center || y == center || x == 0 || y == 0 || x == size - 1 || y == size - 1 {
+            b'.'
+        } else if rng.chance(0.1) {
+            b'#'
+        } else {
+            b'.'
+        }
+    })
+}
+
+fn day22(rng: &mut Rng, size: usize) -> String {
+    let mut occupied = FastSet::default();
+    let mut bricks = vec![];
+    while bricks.len() < size {
+        let start = [
+            rng.range(0..=9),
+            rng.range(0..=9),
+            rng.range(1..=size as i64 * 2),
+        ];
+        let mut end = start;
+        let axis = rng.below(3);
+        end[axis] += rng.range(0..=3);
+        if axis < 2 && end[axis] > 9 {
+            continue;
+        }
+        let cubes: Vec<_> = (start[0]..=end[0])
+            .cartesian_product(start[1]..=end[1])
+            .cartesian_product(start[2]..=end[2])
+            .collect();
+        if cubes.iter().all(|cube| !occupied.contains(cube)) {
+            occupied.extend(cubes);
+            bricks.push(format!(
+                "{},{},{}~{},{},{}",
+                start[0], start[1], start[2], end[0], end[1], end[2]
+            ));
+        }
+    }
+    bricks.join("\n")
+}
+
+fn day23(rng: &mut Rng, size: usize) -> String {
+    // A maze from a random spanning tree of a k x k lattice of rooms, plus a few extra doors
+    // to make loops. The slopes on the doors point away from the start, so that the
+    // slippery hike can still reach the end.
+    let k = (size / 2).max(2);
+    let room = |(i, j): (usize, usize)| (2 * i + 1, 2 * j + 1);
+    let mut edges: Vec<_> = (0..k)
+        .cartesian_product(0..k)
+        .flat_map(|(i, j)| [((i, j), (i + 1, j)), ((i, j), (i, j + 1))])
+        .filter(|&(_, (i, j))| i < k && j < k)
+        .collect();
+    rng.shuffle(&mut edges);
+    let mut tree = UnionFind::new(k * k);
+    let doors: Vec<_> = edges
+        .into_iter()
+        .filter(|&((i1, j1), (i2, j2))| tree.union(j1 * k + i1, j2 * k + i2) || rng.chance(0.15))
+        .collect();
+    let mut neighbors: FastMap<_, Vec<_>> = FastMap::default();
+    for &(a, b) in &doors {
+        neighbors.entry(a).or_default().push(b);
+        neighbors.entry(b).or_default().push(a);
+    }
+    let mut distances = FastMap::default();
+    distances.insert((0, 0), 0);
+    let mut to_visit = std::collections::VecDeque::from([(0, 0)]);
+    while let Some(current) = to_visit.pop_front() {
+        for &next in &neighbors[&current] {
+            if !distances.contains_key(&next) {
+                distances.insert(next, distances[&current] + 1);
+                to_visit.push_back(next);
+            }
+        }
+    }
+    let size = 2 * k + 1;
+    let mut maze = Grid::from_fn(size, size, |_, _| b'#');
+    for i in 0..k {
+        for j in 0..k {
+            let (x, y) = room((i, j));
+            maze.set(x, y, b'.');
+        }
+    }
+    for (a, b) in doors {
+        let (a, b) = if distances[&a] <= distances[&b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let ((xa, ya), (xb, yb)) = (room(a), room(b));
+        let slope = match (distances[&a] == distances[&b], xb > xa, xb < xa, yb > ya) {
+            (true, ..) => b'.',
+            (_, true, _, _) => b'>',
+            (_, _, true, _) => b'<',
+            (_, _, _, true) => b'v',
+            _ => b'^',
+        };
+        maze.set((xa + xb) / 2, (ya + yb) / 2, slope);
+    }
+    maze.set(1, 0, b'.');
+    maze.set(size - 2, size - 1, b'.');
+    random_grid(size, size, |x, y| *maze.get(x, y))
+}
+
+fn day24(rng: &mut Rng, size: usize) -> String {
+    // every hailstone is where the rock will be at some time t, with its own velocity
+    let rock_position = [(); 3].map(|_| rng.range(200_000_000_000_000..=400_000_000_000_000));
+    let rock_velocity = [(); 3].map(|_| rng.range(-300..=300));
+    let mut times = FastSet::default();
+    (0..size.max(3))
+        .map(|_| {
+            let mut time = rng.range(100_000_000_000..=1_000_000_000_000);
+            while !times.insert(time) {
+                time += 1;
+            }
+            let velocity = [(); 3].map(|_| rng.range(-300..=300));
+            let position: [i64; 3] =
+                std::array::from_fn(|i| rock_position[i] + (rock_velocity[i] - velocity[i]) * time);
+            format!(
+                "{}, {}, {} @ {}, {}, {}",
+                position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+            )
+        })
+        .join("\n")
+}
+
+fn day25(rng: &mut Rng, size: usize) -> String {
+    // Two clusters joined by exactly 3 wires. Each cluster is a shuffled ring where every
+    // component is wired to the next two, which takes 4 cuts to split, plus random wires.
+    let cluster_sizes = [size.max(10) / 2, size.max(10) - size.max(10) / 2];
+    let names = unique_names(
+        rng,
+        cluster_sizes[0] + cluster_sizes[1],
+        LOWERCASE,
+        3,
+        |_| true,
+    );
+    let (cluster1, cluster2) = names.split_at(cluster_sizes[0]);
+    let mut wires = FastSet::default();
+    for cluster in [cluster1, cluster2] {
+        let mut ring = cluster.to_vec();
+        rng.shuffle(&mut ring);
+        let n = ring.len();
+        for i in 0..n {
+            wires.insert((ring[i].clone(), ring[(i + 1) % n].clone()));
+            wires.insert((ring[i].clone(), ring[(i + 2) % n].clone()));
+        }
+        for _ in 0..n / 2 {
+            let (a, b) = (rng.choose(&ring).clone(), rng.choose(&ring).clone());
+            if a != b && !wires.contains(&(b.clone(), a.clone())) {
+                wires.insert((a, b));
+            }
+        }
+    }
+    let mut cut_ends1 = cluster1.to_vec();
+    let mut cut_ends2 = cluster2.to_vec();
+    rng.shuffle(&mut cut_ends1);
+    rng.shuffle(&mut cut_ends2);
+    for (a, b) in cut_ends1.into_iter().zip(cut_ends2).take(3) {
+        wires.insert((a, b));
+    }
+    let mut wires: Vec<_> = wires.into_iter().collect();
+    wires.sort();
+    rng.shuffle(&mut wires);
+    let mut connections: FastMap<String, Vec<String>> = FastMap::default();
+    let mut order = vec![];
+    for (a, b) in wires {
+        let (from, to) = if rng.chance(0.5) { (a, b) } else { (b, a) };
+        if !connections.contains_key(&from) {
+            order.push(from.clone());
+        }
+        connections.entry(from).or_default().push(to);
+    }
+    order
+        .iter()
+        .map(|from| format!("{from}: {}", connections[from].join(" ")))
+        .join("\n")
+}
@@ -23,4 +23,6 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+pub mod gen;
+pub mod reference;
 pub mod utils;
@@ -1,9 +1,25 @@
+use aoc2023::day01::DigitVocabulary;
+use aoc2023::reference::Solver;
+use aoc2023::utils::dot::Dot;
+use aoc2023::utils::fast_hash::FxBuildHasher;
+use aoc2023::utils::image::Image;
+use aoc2023::utils::viz::Animation;
+use hashbrown::hash_map::DefaultHashBuilder;
+use std::collections::hash_map::RandomState;
 use std::env;
 use std::fs;
-use std::time::Instant;
+use std::hash::{BuildHasher, Hash};
+use std::hint::black_box;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
 
 fn elapsed_since(start_time: &Instant) -> String {
-    let elapsed = start_time.elapsed().as_micros();
+    format_duration(start_time.elapsed())
+}
+
+fn format_duration(duration: Duration) -> String {
+    let elapsed = duration.as_micros();
     if elapsed >= 1_000_000 {
         let elapsed = elapsed as f64 / 1_000_000.0;
         format!("{elapsed:.1}s")
@@ -15,12 +31,349 @@ fn elapsed_since(start_time: &Instant) -> String {
     }
 }
 
+fn time_map_operations<K: Hash + Eq + Clone, S: BuildHasher + Default>(keys: &[K]) -> String {
+    let start_time = Instant::now();
+    let mut map: hashbrown::HashMap<K, usize, S> = hashbrown::HashMap::default();
+    for _ in 0..10 {
+        for (index, key) in keys.iter().enumerate() {
+            *map.entry(key.clone()).or_insert(index) += 1;
+        }
+        for key in keys {
+            black_box(map.get(key));
+        }
+    }
+    elapsed_since(&start_time)
+}
+
+fn benchmark_hashers<K: Hash + Eq + Clone>(description: &str, keys: &[K]) {
+    println!(
+        "{description} ({} keys): SipHash {}, aHash {}, FxHash {}",
+        keys.len(),
+        time_map_operations::<_, RandomState>(keys),
+        time_map_operations::<_, DefaultHashBuilder>(keys),
+        time_map_operations::<_, FxBuildHasher>(keys),
+    );
+}
+
+fn run_hash_benchmarks() {
+    // typical keys: grid positions (days 10, 21, 23), module names (day 20), hashes (day 14)
+    println!("Hash map benchmarks (10 rounds of insertions and lookups):");
+    let positions: Vec<(i32, i32)> = (0..300)
+        .flat_map(|y| (0..300).map(move |x| (x, y)))
+        .collect();
+    benchmark_hashers("grid positions", &positions);
+    let names: Vec<String> = (0..26 * 26 * 26)
+        .map(|i| {
+            let letters = [i / 676, i / 26 % 26, i % 26].map(|n| (b'a' + n as u8) as char);
+            letters.iter().collect()
+        })
+        .collect();
+    benchmark_hashers("module names", &names);
+    let hashes: Vec<u64> = (0..100_000u64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .collect();
+    benchmark_hashers("state hashes", &hashes);
+
+    // the days that switched to FxHash, with the hasher they used before
+    println!("Days with their previous hasher, then with FxHash (best of 3 runs):");
+    let days: [(u32, &str, Solver, Solver); 3] = [
+        (
+            14,
+            "SipHash",
+            aoc2023::day14::solve_with_hasher::<RandomState>,
+            aoc2023::day14::solve,
+        ),
+        (
+            20,
+            "aHash",
+            aoc2023::day20::solve_with_hasher::<DefaultHashBuilder>,
+            aoc2023::day20::solve,
+        ),
+        (
+            23,
+            "aHash",
+            aoc2023::day23::solve_with_hasher::<DefaultHashBuilder>,
+            aoc2023::day23::solve,
+        ),
+    ];
+    for (day, previous_hasher, previous_solver, solver) in days {
+        let path = format!("./data/day{:02}.txt", day);
+        let Ok(input) = fs::read_to_string(&path) else {
+            println!("Day {day}: ERROR: no data");
+            continue;
+        };
+        let input = input.trim_end();
+        let (previous_answers, previous_time) = time_solver(previous_solver, input);
+        let (answers, time) = time_solver(solver, input);
+        assert_eq!(answers, previous_answers, "Day {day}: the hashers disagree");
+        println!(
+            "Day {day}: {previous_hasher} {}, FxHash {}",
+            format_duration(previous_time),
+            format_duration(time)
+        );
+    }
+}
+
+/// The answers of the solver, and its best time over a few runs
+fn time_solver(solver: Solver, input: &str) -> (Vec<String>, Duration) {
+    let mut best_time = Duration::MAX;
+    let mut answers = vec![];
+    for _ in 0..3 {
+        let start_time = Instant::now();
+        answers = solver(input);
+        best_time = best_time.min(start_time.elapsed());
+    }
+    (answers, best_time)
+}
+
+fn get_visualization(day: u32) -> Option<fn(&str) -> Animation> {
+    match day {
+        10 => Some(aoc2023::day10::visualize),
+        16 => Some(aoc2023::day16::visualize),
+        17 => Some(aoc2023::day17::visualize),
+        23 => Some(aoc2023::day23::visualize),
+        _ => None,
+    }
+}
+
+type RenderFn = fn(&str) -> Vec<(String, Image)>;
+
+fn get_rendering(day: u32) -> Option<RenderFn> {
+    match day {
+        14 => Some(aoc2023::day14::render),
+        18 => Some(aoc2023::day18::render),
+        21 => Some(aoc2023::day21::render),
+        _ => None,
+    }
+}
+
+fn render_images(day: u32, input: &str, directory: &str) {
+    let render = match get_rendering(day) {
+        Some(render) => render,
+        None => {
+            eprintln!("Day {day}: no images");
+            return;
+        }
+    };
+    fs::create_dir_all(directory).unwrap();
+    for (name, image) in render(input) {
+        let path = Path::new(directory).join(format!("day{day:02}_{name}.png"));
+        image.save(&path).unwrap();
+        println!("Day {day}: saved {}", path.display());
+    }
+}
+
+fn get_graph(day: u32) -> Option<fn(&str) -> Dot> {
+    match day {
+        8 => Some(aoc2023::day08::to_dot),
+        19 => Some(aoc2023::day19::to_dot),
+        20 => Some(aoc2023::day20::to_dot),
+        23 => Some(aoc2023::day23::to_dot),
+        25 => Some(aoc2023::day25::to_dot),
+        _ => None,
+    }
+}
+
+/// The days that can trace how they get their answers
+fn get_trace(day: u32) -> Option<fn(&str) -> String> {
+    match day {
+        2 => Some(aoc2023::day02::trace),
+        4 => Some(|input| aoc2023::day04::trace(input).to_string()),
+        5 => Some(|input| match aoc2023::day05::seed_to_location(input) {
+            Ok((_, map)) => format!("{map}\n"),
+            Err(error) => format!("Invalid almanac: {error}\n"),
+        }),
+        12 => Some(aoc2023::day12::trace),
+        _ => None,
+    }
+}
+
+/// A preset digit vocabulary for day 1, or one loaded from a file
+fn load_vocabulary(name_or_path: &str) -> DigitVocabulary {
+    DigitVocabulary::preset(name_or_path).unwrap_or_else(|| {
+        DigitVocabulary::load(name_or_path).unwrap_or_else(|error| {
+            panic!(
+                "Invalid vocabulary {name_or_path} (presets: {}): {error}",
+                DigitVocabulary::PRESETS.join(", ")
+            )
+        })
+    })
+}
+
+/// The speed of the visualizations, which must be a positive number of frames per second
+fn parse_frames_per_second(arg: Option<&String>) -> f64 {
+    match arg.and_then(|arg| arg.parse::<f64>().ok()) {
+        Some(fps) if fps > 0.0 && fps.is_finite() => fps,
+        _ => {
+            eprintln!("Usage: --fps N, where N is a positive number of frames per second");
+            process::exit(2);
+        }
+    }
+}
+
+/// Prints a random input, with the arguments: DAY [--seed S] [--size N]
+fn generate_input(args: &[String]) {
+    let day: u32 = args.first().expect("Missing day").parse().unwrap();
+    let mut seed = 1;
+    let mut size = 10;
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = args.next().unwrap().parse().unwrap(),
+            "--size" => size = args.next().unwrap().parse().unwrap(),
+            _ => panic!("Unknown argument: {arg}"),
+        }
+    }
+    match aoc2023::gen::generate(day, seed, size) {
+        Some(input) => println!("{input}"),
+        None => eprintln!("Day {day}: no generator"),
+    }
+}
+
+/// Runs the hand-written cases, and compares the fast and reference solvers on generated
+/// inputs, with the arguments:
+/// [DAYS...] [--seeds N]
+fn check_solvers(args: &[String]) {
+    let mut num_seeds = 20;
+    let mut days: Vec<u32> = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seeds" => num_seeds = args.next().unwrap().parse().unwrap(),
+            _ => days.push(arg.parse().unwrap()),
+        }
+    }
+    if days.is_empty() {
+        days = (1..=25).collect();
+    }
+    let format_answers = |answers: &Option<Vec<String>>| match answers {
+        Some(answers) => answers.join(", "),
+        None => "panicked or timed out".to_string(),
+    };
+    for day in days {
+        let start_time = Instant::now();
+        let num_cases = match aoc2023::reference::check_cases(day) {
+            Ok(num_cases) => num_cases,
+            Err(failure) => {
+                println!("Day {day}: FAILED case \"{}\"", failure.case.name);
+                println!("Expected:  {}", failure.case.answers.join(", "));
+                println!("Got:       {}", format_answers(&failure.answers));
+                println!("Input:\n{}", failure.case.input);
+                continue;
+            }
+        };
+        let cases = match num_cases {
+            0 => String::new(),
+            1 => " and 1 case".to_string(),
+            _ => format!(" and {num_cases} cases"),
+        };
+        match aoc2023::reference::differential_test(day, num_seeds) {
+            None => println!("Day {day}: no reference solver"),
+            Some(Ok(num_checked)) => println!(
+                "Day {day}: OK, {num_checked} inputs{cases} ({})",
+                elapsed_since(&start_time)
+            ),
+            Some(Err(disagreement)) => {
+                println!(
+                    "Day {day}: MISMATCH (seed {}, size {})",
+                    disagreement.seed, disagreement.size
+                );
+                println!("Fast:      {}", format_answers(&disagreement.fast_answers));
+                println!(
+                    "Reference: {}",
+                    format_answers(&disagreement.reference_answers)
+                );
+                println!("Minimized input:\n{}", disagreement.input);
+            }
+        }
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let days: Vec<_> = match args.len() {
-        1 => (1..=25).collect(),
-        _ => args.iter().skip(1).map(|d| d.parse().unwrap()).collect(),
-    };
+    if args.get(1).is_some_and(|arg| arg == "--bench-hash") {
+        run_hash_benchmarks();
+        return;
+    }
+    if args.get(1).is_some_and(|arg| arg == "generate") {
+        generate_input(&args[2..]);
+        return;
+    }
+    if args.get(1).is_some_and(|arg| arg == "check") {
+        check_solvers(&args[2..]);
+        return;
+    }
+    let mut is_viz = false;
+    let mut is_dot = false;
+    let mut frames_per_second = 10.0;
+    let mut render_directory = None;
+    let mut vocabulary = None;
+    let mut is_trace = false;
+    let mut days: Vec<u32> = vec![];
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--viz" => is_viz = true,
+            "--dot" => is_dot = true,
+            "--trace" => is_trace = true,
+            "--fps" => frames_per_second = parse_frames_per_second(args.next()),
+            "--render" => render_directory = Some(args.next().unwrap().clone()),
+            "--vocabulary" => vocabulary = Some(load_vocabulary(args.next().unwrap())),
+            _ => days.push(arg.parse().unwrap()),
+        }
+    }
+    let is_every_day = days.is_empty();
+    if is_every_day {
+        days = (1..=25).collect();
+    }
+    if let Some(directory) = render_directory {
+        for &day in &days {
+            let path = format!("./data/day{:02}.txt", day);
+            match fs::read_to_string(&path) {
+                Ok(input) => render_images(day, input.trim_end(), &directory),
+                Err(_) => eprintln!("Day {day}: ERROR: no data"),
+            }
+        }
+        return;
+    }
+    if is_trace {
+        if !is_every_day {
+            if let Some(day) = days.iter().find(|&&day| get_trace(day).is_none()) {
+                eprintln!("Day {day}: no trace");
+                process::exit(1);
+            }
+        }
+        for &day in days.iter().filter(|&&day| get_trace(day).is_some()) {
+            let path = format!("./data/day{:02}.txt", day);
+            match fs::read_to_string(&path) {
+                Ok(input) => print!("{}", get_trace(day).unwrap()(input.trim_end())),
+                Err(_) => eprintln!("Day {day}: ERROR: no data"),
+            }
+        }
+        return;
+    }
+    if is_dot {
+        for &day in &days {
+            let path = format!("./data/day{:02}.txt", day);
+            match (get_graph(day), fs::read_to_string(&path)) {
+                (None, _) => eprintln!("Day {day}: no graph"),
+                (_, Err(_)) => eprintln!("Day {day}: ERROR: no data"),
+                (Some(to_dot), Ok(input)) => println!("{}", to_dot(input.trim_end())),
+            }
+        }
+        return;
+    }
+    if is_viz {
+        for &day in &days {
+            let path = format!("./data/day{:02}.txt", day);
+            match (get_visualization(day), fs::read_to_string(&path)) {
+                (None, _) => eprintln!("Day {day}: no visualization"),
+                (_, Err(_)) => eprintln!("Day {day}: ERROR: no data"),
+                (Some(visualize), Ok(input)) => visualize(input.trim_end()).play(frames_per_second),
+            }
+        }
+        return;
+    }
     let global_start_time = Instant::now();
     for day in &days {
         println!("Day {}:", day);
@@ -30,34 +383,40 @@ fn main() {
         if let Ok(input) = input {
             let input = input.trim_end();
             let day_func = match day {
-                1 => aoc2023::day01::run,
-                2 => aoc2023::day02::run,
-                3 => aoc2023::day03::run,
-                4 => aoc2023::day04::run,
-                5 => aoc2023::day05::run,
-                6 => aoc2023::day06::run,
-                7 => aoc2023::day07::run,
-                8 => aoc2023::day08::run,
-                9 => aoc2023::day09::run,
-                10 => aoc2023::day10::run,
-                11 => aoc2023::day11::run,
-                12 => aoc2023::day12::run,
-                13 => aoc2023::day13::run,
-                14 => aoc2023::day14::run,
-                15 => aoc2023::day15::run,
-                16 => aoc2023::day16::run,
-                17 => aoc2023::day17::run,
-                18 => aoc2023::day18::run,
-                19 => aoc2023::day19::run,
-                20 => aoc2023::day20::run,
-                21 => aoc2023::day21::run,
-                22 => aoc2023::day22::run,
-                23 => aoc2023::day23::run,
-                24 => aoc2023::day24::run,
-                25 => aoc2023::day25::run,
+                1 => aoc2023::day01::solve,
+                2 => aoc2023::day02::solve,
+                3 => aoc2023::day03::solve,
+                4 => aoc2023::day04::solve,
+                5 => aoc2023::day05::solve,
+                6 => aoc2023::day06::solve,
+                7 => aoc2023::day07::solve,
+                8 => aoc2023::day08::solve,
+                9 => aoc2023::day09::solve,
+                10 => aoc2023::day10::solve,
+                11 => aoc2023::day11::solve,
+                12 => aoc2023::day12::solve,
+                13 => aoc2023::day13::solve,
+                14 => aoc2023::day14::solve,
+                15 => aoc2023::day15::solve,
+                16 => aoc2023::day16::solve,
+                17 => aoc2023::day17::solve,
+                18 => aoc2023::day18::solve,
+                19 => aoc2023::day19::solve,
+                20 => aoc2023::day20::solve,
+                21 => aoc2023::day21::solve,
+                22 => aoc2023::day22::solve,
+                23 => aoc2023::day23::solve,
+                24 => aoc2023::day24::solve,
+                25 => aoc2023::day25::solve,
                 _ => unreachable!(),
             };
-            day_func(input);
+            let answers = match (day, &vocabulary) {
+                (1, Some(vocabulary)) => aoc2023::day01::solve_with_vocabulary(input, vocabulary),
+                _ => day_func(input),
+            };
+            for answer in answers {
+                println!("{answer}");
+            }
             println!("Time: {}", elapsed_since(&start_time));
         } else {
             println!("ERROR: no data");
@@ -0,0 +1,234 @@
+//! Slow but straightforward solvers (simulations, exhaustive searches...) that make none of the
+//! assumptions of the optimized ones, and a differential test harness comparing both on
+//! generated inputs.
+
+use crate::gen;
+
+use std::panic;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub type Solver = fn(&str) -> Vec<String>;
+
+/// The two solvers to compare for a day, and the largest input size the reference solver
+/// can handle quickly
+#[derive(Debug, Clone, Copy)]
+pub struct Solvers {
+    pub fast: Solver,
+    pub reference: Solver,
+    pub max_size: usize,
+}
+
+pub fn solvers(day: u32) -> Option<Solvers> {
+    let (fast, reference, max_size): (Solver, Solver, usize) = match day {
+        1 => (crate::day01::solve, day01::solve, 12),
+        2 => (
+            day02::fast_solve_with_estimates,
+            day02::solve_with_estimates,
+            12,
+        ),
+        3 => (crate::day03::solve, day03::solve, 12),
+        4 => (crate::day04::solve, day04::solve, 8),
+        5 => (day05::fast_solve_with_queries, day05::solve_with_queries, 8),
+        6 => (crate::day06::solve, day06::solve, 3),
+        7 => (crate::day07::solve, day07::solve, 12),
+        8 => (crate::day08::solve, day08::solve, 8),
+        9 => (crate::day09::solve, day09::solve, 12),
+        10 => (crate::day10::solve, day10::solve, 12),
+        11 => (crate::day11::solve, day11::solve, 12),
+        12 => (crate::day12::solve, day12::solve, 8),
+        13 => (crate::day13::solve, day13::solve, 12),
+        14 => (crate::day14::solve, day14::solve, 12),
+        15 => (crate::day15::solve, day15::solve, 12),
+        16 => (crate::day16::solve, day16::solve, 12),
+        17 => (crate::day17::solve, day17::solve, 12),
+        18 => (crate::day18::solve, day18::solve, 12),
+        19 => (crate::day19::solve, day19::solve, 12),
+        20 => (crate::day20::solve, day20::solve, 4),
+        21 => (day21::fast_solve, day21::solve, 12),
+        22 => (crate::day22::solve, day22::solve, 12),
+        23 => (crate::day23::solve, day23::solve, 8),
+        24 => (crate::day24::solve, day24::solve, 12),
+        25 => (crate::day25::solve, day25::solve, 20),
+        _ => return None,
+    };
+    Some(Solvers {
+        fast,
+        reference,
+        max_size,
+    })
+}
+
+/// A hand-written input, with the answers a solver must give, for what the generators don't
+/// produce (or only by chance)
+#[derive(Debug, Clone, Copy)]
+pub struct Case {
+    pub name: &'static str,
+    pub solver: Solver,
+    pub input: &'static str,
+    pub answers: &'static [&'static str],
+}
+
+pub fn cases(day: u32) -> Vec<Case> {
+    match day {
+        1 => day01::cases(),
+        2 => day02::cases(),
+        4 => day04::cases(),
+        5 => day05::cases(),
+        6 => day06::cases(),
+        _ => vec![],
+    }
+}
+
+/// A case whose solver gave other answers (None if it panicked or took too long)
+#[derive(Debug, Clone)]
+pub struct CaseFailure {
+    pub case: Case,
+    pub answers: Option<Vec<String>>,
+}
+
+/// Runs the function with the panic messages silenced (the solvers may panic on purpose)
+fn silently<T>(f: impl FnOnce() -> T) -> T {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(default_hook);
+    result
+}
+
+/// Runs the hand-written cases of the day. Returns the number of cases, or the first failure.
+pub fn check_cases(day: u32) -> Result<usize, CaseFailure> {
+    let cases = cases(day);
+    silently(|| {
+        for &case in &cases {
+            let answers = run_solver(case.solver, case.input);
+            let is_expected = answers
+                .as_ref()
+                .is_some_and(|answers| answers.iter().eq(case.answers));
+            if !is_expected {
+                return Err(CaseFailure { case, answers });
+            }
+        }
+        Ok(cases.len())
+    })
+}
+
+/// The answers of a solver, or None if it panicked or took too long
+fn run_solver(solver: Solver, input: &str) -> Option<Vec<String>> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    // a solver stuck in an infinite loop is left behind, it will die with the process
+    thread::spawn(move || sender.send(solver(&input)));
+    receiver.recv_timeout(Duration::from_secs(2)).ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub fast_answers: Option<Vec<String>>,
+    pub reference_answers: Option<Vec<String>>,
+}
+
+impl Disagreement {
+    fn check(solvers: &Solvers, seed: u64, size: usize, input: String) -> Option<Self> {
+        let fast_answers = run_solver(solvers.fast, &input);
+        let reference_answers = run_solver(solvers.reference, &input);
+        if fast_answers == reference_answers && fast_answers.is_some() {
+            return None;
+        }
+        Some(Self {
+            seed,
+            size,
+            input,
+            fast_answers,
+            reference_answers,
+        })
+    }
+
+    /// Whether another disagreement is of the same kind (the same solvers failing, if any)
+    fn is_similar(&self, other: &Disagreement) -> bool {
+        self.fast_answers.is_some() == other.fast_answers.is_some()
+            && self.reference_answers.is_some() == other.reference_answers.is_some()
+    }
+
+    /// Removes chunks of lines from the input, as long as the solvers still disagree
+    fn minimize(self, solvers: &Solvers) -> Self {
+        let mut smallest = self;
+        let mut chunk_size = smallest.input.lines().count() / 2;
+        while chunk_size > 0 {
+            let mut is_reduced = false;
+            let mut start = 0;
+            while start < smallest.input.lines().count() {
+                let lines: Vec<_> = smallest.input.lines().collect();
+                let end = (start + chunk_size).min(lines.len());
+                let input = [&lines[..start], &lines[end..]].concat().join("\n");
+                let candidate = if input.trim().is_empty() {
+                    None
+                } else {
+                    Self::check(solvers, smallest.seed, smallest.size, input)
+                };
+                match candidate {
+                    Some(candidate) if candidate.is_similar(&smallest) => {
+                        smallest = candidate;
+                        is_reduced = true;
+                    }
+                    _ => start += chunk_size,
+                }
+            }
+            if !is_reduced {
+                chunk_size /= 2;
+            }
+        }
+        smallest
+    }
+}
+
+/// Compares the fast and reference solvers of the day on generated inputs, of every size up to
+/// the maximum one and for every seed in 0..num_seeds. Returns the number of inputs checked,
+/// or the first disagreement (with the smallest size), minimized.
+pub fn differential_test(day: u32, num_seeds: u64) -> Option<Result<usize, Disagreement>> {
+    let solvers = solvers(day)?;
+    let result = silently(|| {
+        let mut num_checked = 0;
+        for size in 1..=solvers.max_size {
+            for seed in 0..num_seeds {
+                let input = gen::generate(day, seed, size).unwrap();
+                if let Some(disagreement) = Disagreement::check(&solvers, seed, size, input) {
+                    return Err(disagreement.minimize(&solvers));
+                }
+                num_checked += 1;
+            }
+        }
+        Ok(num_checked)
+    });
+    Some(result)
+}
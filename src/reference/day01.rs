@@ -0,0 +1,47 @@
+use super::Case;
+use crate::day01::{solve_with_vocabulary, DigitVocabulary};
+
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The digits of the line in order, found by trying every digit and word at every position
+fn find_digits(line: &str, words: &[&str]) -> Vec<u32> {
+    (0..line.len())
+        .filter_map(|index| {
+            let rest = &line[index..];
+            let c = rest.as_bytes()[0];
+            if c.is_ascii_digit() {
+                return Some((c - b'0') as u32);
+            }
+            let position = words.iter().position(|word| rest.starts_with(word))?;
+            Some(position as u32 + 1)
+        })
+        .collect()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    [&[][..], &DIGIT_NAMES[..]]
+        .map(|words| {
+            input
+                .lines()
+                .map(|line| {
+                    let digits = find_digits(line, words);
+                    digits[0] * 10 + digits[digits.len() - 1]
+                })
+                .sum::<u32>()
+                .to_string()
+        })
+        .to_vec()
+}
+
+/// Roman numerals, where a numeral can end with shorter ones ("VIII" ends with "I", and "IV"
+/// with "V"): the last digit must be the longest one ending last
+pub fn cases() -> Vec<Case> {
+    vec![Case {
+        name: "overlapping roman numerals",
+        solver: |input| solve_with_vocabulary(input, &DigitVocabulary::roman()),
+        input: "1VIII\n2IV",
+        answers: &["33", "42"],
+    }]
+}
@@ -0,0 +1,164 @@
+use super::Case;
+
+use itertools::Itertools;
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut sum_possible_ids = 0;
+    let mut sum_powers = 0;
+    for line in input.lines() {
+        let (game, draws) = line.split_once(": ").unwrap();
+        let game_id: u32 = game.strip_prefix("Game ").unwrap().parse().unwrap();
+        let mut is_possible = true;
+        let mut max_counts = [0; 3];
+        for balls in draws.split("; ").flat_map(|draw| draw.split(", ")) {
+            let (count, color) = balls.split_once(' ').unwrap();
+            let count: u32 = count.parse().unwrap();
+            let color = ["red", "green", "blue"]
+                .iter()
+                .position(|&name| name == color)
+                .unwrap();
+            is_possible &= count <= [12, 13, 14][color];
+            max_counts[color] = max_counts[color].max(count);
+        }
+        if is_possible {
+            sum_possible_ids += game_id;
+        }
+        sum_powers += max_counts.iter().product::<u32>();
+    }
+    vec![sum_possible_ids.to_string(), sum_powers.to_string()]
+}
+
+/// The binomial coefficients up to n, by Pascal's rule
+fn pascal_triangle(n: u32) -> Vec<Vec<u128>> {
+    let mut rows = vec![vec![1]];
+    for _ in 0..n {
+        let previous = rows.last().unwrap();
+        let row = (0..=previous.len())
+            .map(|k| {
+                let left = if k > 0 { previous[k - 1] } else { 0 };
+                left + previous.get(k).unwrap_or(&0)
+            })
+            .collect();
+        rows.push(row);
+    }
+    rows
+}
+
+/// The cubes of every set of every game, as [red, green, blue] counts
+fn parse_sets(input: &str) -> Vec<Vec<[u32; 3]>> {
+    input
+        .lines()
+        .map(|line| {
+            let (_, sets) = line.split_once(": ").unwrap();
+            sets.split("; ")
+                .map(|set| {
+                    let mut counts = [0; 3];
+                    for balls in set.split(", ") {
+                        let (count, color) = balls.split_once(' ').unwrap();
+                        let color = ["red", "green", "blue"]
+                            .iter()
+                            .position(|&name| name == color)
+                            .unwrap();
+                        counts[color] += count.parse::<u32>().unwrap();
+                    }
+                    counts
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The bag with the highest probability to give exactly the sets of the game, among all the
+/// bags of at most max_cubes cubes of the three colors, counting the ways to draw each set
+fn estimate_bag(sets: &[[u32; 3]], max_cubes: u32) -> ([u32; 3], f64) {
+    let pascal = pascal_triangle(max_cubes);
+    let choose = |n: u32, k: u32| *pascal[n as usize].get(k as usize).unwrap_or(&0);
+    let mut best: Option<([u32; 3], f64)> = None;
+    for red in 0..=max_cubes {
+        for green in 0..=max_cubes - red {
+            for blue in 0..=max_cubes - red - green {
+                let bag = [red, green, blue];
+                let probability: f64 = sets
+                    .iter()
+                    .map(|set| {
+                        let num_ways: u128 = (0..3).map(|i| choose(bag[i], set[i])).product();
+                        let num_draws = choose(bag.iter().sum(), set.iter().sum());
+                        if num_ways == 0 {
+                            0.0
+                        } else {
+                            num_ways as f64 / num_draws as f64
+                        }
+                    })
+                    .product();
+                // ties go to the smallest bag, then to the fewest blue cubes, then green cubes
+                let tie_breaker = |[red, green, blue]: [u32; 3]| (red + green + blue, blue, green);
+                let is_better = best.is_none_or(|(best_bag, best_probability)| {
+                    probability > best_probability
+                        || (probability == best_probability
+                            && tie_breaker(bag) < tie_breaker(best_bag))
+                });
+                if is_better {
+                    best = Some((bag, probability));
+                }
+            }
+        }
+    }
+    best.unwrap()
+}
+
+/// After the answers, the solvers give the most plausible bag of every game, among the bags
+/// with at most that many more cubes than the minimum bag of the game
+const EXTRA_CUBES: u32 = 3;
+
+/// A bag and its probability, without the colors it doesn't have
+fn format_estimate(
+    counts: impl IntoIterator<Item = (u32, &'static str)>,
+    probability: f64,
+) -> String {
+    let counts = counts
+        .into_iter()
+        .filter(|&(count, _)| count > 0)
+        .map(|(count, color)| format!("{count} {color}"))
+        .join(", ");
+    format!("{counts} ({probability:.6e})")
+}
+
+/// The optimized solver, followed by the bag estimates of the games
+pub fn fast_solve_with_estimates(input: &str) -> Vec<String> {
+    let estimates = input.lines().map(|line| {
+        let game = crate::day02::parse_line(line);
+        let max_cubes = game.minimum_bag().num_cubes() + EXTRA_CUBES;
+        let (bag, probability) = crate::day02::estimate_bag(&game, max_cubes).unwrap();
+        let counts = ["blue", "green", "red"].map(|color| (bag.count(color), color));
+        format_estimate(counts, probability)
+    });
+    crate::day02::solve(input)
+        .into_iter()
+        .chain(estimates)
+        .collect()
+}
+
+/// The reference solver, followed by the bag estimates of the games, found by trying every bag
+pub fn solve_with_estimates(input: &str) -> Vec<String> {
+    let estimates = parse_sets(input).into_iter().map(|sets| {
+        let minimum_bag_size: u32 = (0..3)
+            .map(|i| sets.iter().map(|set| set[i]).max().unwrap())
+            .sum();
+        let ([red, green, blue], probability) = estimate_bag(&sets, minimum_bag_size + EXTRA_CUBES);
+        format_estimate(
+            [(blue, "blue"), (green, "green"), (red, "red")],
+            probability,
+        )
+    });
+    solve(input).into_iter().chain(estimates).collect()
+}
+
+/// Games whose most plausible bags are tied
+pub fn cases() -> Vec<Case> {
+    vec![Case {
+        name: "bags equally likely",
+        solver: fast_solve_with_estimates,
+        input: "Game 1: 20 green; 2 green, 11 red, 11 blue",
+        answers: &["0", "2420", "11 blue, 22 green, 12 red (5.353051e-20)"],
+    }]
+}
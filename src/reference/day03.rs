@@ -0,0 +1,67 @@
+/// A number of the schematic: its value, row, and first and last columns
+struct Number {
+    value: u64,
+    y: usize,
+    x_start: usize,
+    x_end: usize,
+}
+
+impl Number {
+    fn is_adjacent(&self, x: usize, y: usize) -> bool {
+        y + 1 >= self.y && y <= self.y + 1 && x + 1 >= self.x_start && x <= self.x_end + 1
+    }
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let schematic: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let mut numbers = vec![];
+    for (y, row) in schematic.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if row[x].is_ascii_digit() {
+                let x_start = x;
+                let mut value = 0;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    value = value * 10 + (row[x] - b'0') as u64;
+                    x += 1;
+                }
+                numbers.push(Number {
+                    value,
+                    y,
+                    x_start,
+                    x_end: x - 1,
+                });
+            } else {
+                x += 1;
+            }
+        }
+    }
+    let symbols: Vec<_> = schematic
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &c)| c != b'.' && !c.is_ascii_digit())
+                .map(move |(x, &c)| (x, y, c))
+        })
+        .collect();
+
+    let sum_part_numbers: u64 = numbers
+        .iter()
+        .filter(|number| symbols.iter().any(|&(x, y, _)| number.is_adjacent(x, y)))
+        .map(|number| number.value)
+        .sum();
+    let sum_gear_ratios: u64 = symbols
+        .iter()
+        .filter(|&&(_, _, c)| c == b'*')
+        .filter_map(|&(x, y, _)| {
+            let adjacent: Vec<_> = numbers
+                .iter()
+                .filter(|number| number.is_adjacent(x, y))
+                .collect();
+            (adjacent.len() == 2).then(|| adjacent[0].value * adjacent[1].value)
+        })
+        .sum();
+    vec![sum_part_numbers.to_string(), sum_gear_ratios.to_string()]
+}
@@ -0,0 +1,43 @@
+use super::Case;
+use crate::utils::FastSet;
+
+pub fn solve(input: &str) -> Vec<String> {
+    let num_matching: Vec<usize> = input
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(": ").unwrap();
+            let (winning_numbers, our_numbers) = numbers.split_once(" | ").unwrap();
+            let winning_numbers: FastSet<_> = winning_numbers.split_whitespace().collect();
+            our_numbers
+                .split_whitespace()
+                .filter(|number| winning_numbers.contains(number))
+                .count()
+        })
+        .collect();
+    let points: u64 = num_matching
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| 1 << (count - 1))
+        .sum();
+
+    // process every card, original or copy, one at a time
+    let mut cards_to_process: Vec<_> = (0..num_matching.len()).collect();
+    let mut num_cards = 0u64;
+    while let Some(card) = cards_to_process.pop() {
+        num_cards += 1;
+        let last_copy = (card + num_matching[card]).min(num_matching.len() - 1);
+        cards_to_process.extend(card + 1..=last_copy);
+    }
+    vec![points.to_string(), num_cards.to_string()]
+}
+
+/// Cards with repeated numbers: each of our numbers counts if it is a winning number, even a
+/// repeated one, but a repeated winning number doesn't count twice
+pub fn cases() -> Vec<Case> {
+    vec![Case {
+        name: "repeated numbers",
+        solver: crate::day04::solve,
+        input: "Card 1: 1 2 2 | 2 2 3\nCard 2: 4 4 | 4 5\nCard 3: 6 | 7",
+        answers: &["3", "7"],
+    }]
+}
@@ -0,0 +1,223 @@
+use super::Case;
+use crate::day05::{parse_layers, PiecewiseMap};
+
+use itertools::Itertools;
+use std::ops::Range;
+
+/// Sends a value through the maps, one category after the other
+fn find_location(maps: &[Vec<[i64; 3]>], seed: i64) -> i64 {
+    maps.iter().fold(seed, |value, map| {
+        map.iter()
+            .find(|&&[_, source, length]| (source..source + length).contains(&value))
+            .map_or(value, |&[destination, source, _]| {
+                value - source + destination
+            })
+    })
+}
+
+type Maps = Vec<Vec<[i64; 3]>>;
+
+fn parse(input: &str) -> (Vec<i64>, Maps) {
+    let mut blocks = input.split("\n\n");
+    let seeds: Vec<i64> = blocks.next().unwrap()["seeds: ".len()..]
+        .split(' ')
+        .map(|number| number.parse().unwrap())
+        .collect();
+    let maps: Maps = blocks
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let numbers: Vec<i64> = line
+                        .split(' ')
+                        .map(|number| number.parse().unwrap())
+                        .collect();
+                    [numbers[0], numbers[1], numbers[2]]
+                })
+                .collect()
+        })
+        .collect();
+    (seeds, maps)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let (seeds, maps) = parse(input);
+    let lowest_location = seeds
+        .iter()
+        .map(|&seed| find_location(&maps, seed))
+        .min()
+        .unwrap();
+    // try every seed of every range
+    let lowest_location_of_ranges = seeds
+        .chunks(2)
+        .flat_map(|range| range[0]..range[0] + range[1])
+        .map(|seed| find_location(&maps, seed))
+        .min()
+        .unwrap();
+    vec![
+        lowest_location.to_string(),
+        lowest_location_of_ranges.to_string(),
+    ]
+}
+
+/// Sorts and merges the ranges (the adjacent ones too), and formats them
+fn format_ranges(ranges: impl IntoIterator<Item = Range<i64>>) -> String {
+    let mut ranges = ranges
+        .into_iter()
+        .sorted_by_key(|range| range.start)
+        .collect_vec();
+    ranges.dedup_by(|next, previous| {
+        if next.start <= previous.end {
+            previous.end = previous.end.max(next.end);
+            true
+        } else {
+            false
+        }
+    });
+    ranges.iter().map(|range| format!("{range:?}")).join(",")
+}
+
+/// The questions asked to the seed-to-location map, after the answers: the values sent to each
+/// answer, the image of the seed ranges, and whether the map is a bijection
+fn queries_answers(
+    answers: Vec<String>,
+    preimage: impl Fn(i64) -> Vec<i64>,
+    image: String,
+    is_bijection: bool,
+) -> Vec<String> {
+    let preimages = answers
+        .iter()
+        .map(|answer| preimage(answer.parse().unwrap()).iter().join(","))
+        .collect_vec();
+    [answers, preimages, vec![image, is_bijection.to_string()]].concat()
+}
+
+/// The optimized solver, followed by the same queries answered with the composed map (whose
+/// inverse must give back the identity both ways)
+pub fn fast_solve_with_queries(input: &str) -> Vec<String> {
+    let (seeds, map) = crate::day05::seed_to_location(input).unwrap();
+    let image = format_ranges(
+        seeds
+            .chunks(2)
+            .flat_map(|range| map.apply_range(range[0]..range[0] + range[1])),
+    );
+    let inverse = map.inverse();
+    if let Some(inverse) = &inverse {
+        assert_eq!(map.then(inverse), PiecewiseMap::identity());
+        assert_eq!(inverse.then(&map), PiecewiseMap::identity());
+    }
+    queries_answers(
+        crate::day05::solve(input),
+        |location| map.preimage(location),
+        image,
+        inverse.is_some(),
+    )
+}
+
+/// The reference solver, followed by the queries answered by trying every value. The maps
+/// leave the values outside 0..bound unchanged, with bound the end of the furthest range.
+pub fn solve_with_queries(input: &str) -> Vec<String> {
+    let (seeds, maps) = parse(input);
+    let bound = maps
+        .iter()
+        .flatten()
+        .map(|&[destination, source, length]| destination.max(source) + length)
+        .max()
+        .unwrap_or(0);
+    let preimage = |location: i64| {
+        let mut values = (0..bound)
+            .filter(|&value| find_location(&maps, value) == location)
+            .collect_vec();
+        if !(0..bound).contains(&location) {
+            values.push(location);
+            values.sort();
+        }
+        values
+    };
+    let image = format_ranges(
+        seeds
+            .chunks(2)
+            .flat_map(|range| range[0]..range[0] + range[1])
+            .map(|seed| {
+                let location = find_location(&maps, seed);
+                location..location + 1
+            }),
+    );
+    let mut locations = (0..bound)
+        .map(|value| find_location(&maps, value))
+        .collect_vec();
+    locations.sort();
+    let is_bijection = locations.into_iter().eq(0..bound);
+    queries_answers(solve(input), preimage, image, is_bijection)
+}
+
+/// The validation of the maps, as a single answer
+fn validate(input: &str) -> Vec<String> {
+    match parse_layers(input) {
+        Ok(_) => vec!["valid".to_string()],
+        Err(error) => vec![error.to_string()],
+    }
+}
+
+/// The error of the composed seed-to-location map, which the fast solvers rely on
+fn validate_composed(input: &str) -> Vec<String> {
+    match crate::day05::seed_to_location(input) {
+        Ok(_) => vec!["valid".to_string()],
+        Err(error) => vec![error.to_string()],
+    }
+}
+
+/// Almanacs where parts of the seed ranges fall outside all the maps, or with unusual maps
+pub fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            name: "leading gap",
+            solver: crate::day05::solve,
+            input: "seeds: 0 10\n\nseed-to-location map:\n100 5 10",
+            answers: &["0", "0"],
+        },
+        Case {
+            name: "trailing gap",
+            solver: crate::day05::solve,
+            input: "seeds: 10 10\n\nseed-to-location map:\n50 0 15",
+            answers: &["60", "15"],
+        },
+        Case {
+            name: "no overlapping map",
+            solver: crate::day05::solve,
+            input: "seeds: 20 5\n\nseed-to-location map:\n0 0 10",
+            answers: &["5", "20"],
+        },
+        Case {
+            name: "unsorted maps with a gap between them",
+            solver: crate::day05::solve,
+            input: "seeds: 0 30\n\nseed-to-location map:\n100 20 10\n200 0 10",
+            answers: &["30", "10"],
+        },
+        Case {
+            name: "empty layer",
+            solver: crate::day05::solve,
+            input: "seeds: 3 4\n\nseed-to-soil map:\n\nsoil-to-location map:\n10 0 5",
+            answers: &["13", "5"],
+        },
+        Case {
+            name: "bijective map",
+            solver: fast_solve_with_queries,
+            input: "seeds: 0 4\n\nseed-to-location map:\n2 0 2\n0 2 2",
+            answers: &["2", "0", "0", "2", "0..4", "true"],
+        },
+        Case {
+            name: "overlapping maps",
+            solver: validate,
+            input: "seeds: 1 1\n\nseed-to-location map:\n0 3 4\n2 5 3",
+            answers: &["source ranges 3..7 and 5..8 overlap"],
+        },
+        Case {
+            name: "overlapping maps after a valid category",
+            solver: validate_composed,
+            input: "seeds: 1 1\n\nseed-to-soil map:\n5 0 5\n\nsoil-to-location map:\n0 6 2\n9 6 1",
+            answers: &["source ranges 6..8 and 6..7 overlap"],
+        },
+    ]
+}
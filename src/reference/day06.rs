@@ -0,0 +1,33 @@
+use super::Case;
+
+/// Tries every time the button can be held
+fn count_ways_to_win(time: u64, record_distance: u64) -> u64 {
+    (0..=time)
+        .filter(|&hold_time| hold_time * (time - hold_time) > record_distance)
+        .count() as u64
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let lines: Vec<Vec<&str>> = input
+        .lines()
+        .map(|line| line.split_whitespace().skip(1).collect())
+        .collect();
+    let parse = |number: &str| number.parse::<u64>().unwrap();
+    let product_of_ways: u64 = lines[0]
+        .iter()
+        .zip(&lines[1])
+        .map(|(&time, &distance)| count_ways_to_win(parse(time), parse(distance)))
+        .product();
+    let num_ways = count_ways_to_win(parse(&lines[0].concat()), parse(&lines[1].concat()));
+    vec![product_of_ways.to_string(), num_ways.to_string()]
+}
+
+/// Races too long for the squared times to fit in 64 bits
+pub fn cases() -> Vec<Case> {
+    vec![Case {
+        name: "race times over 3e9",
+        solver: crate::day06::solve,
+        input: "Time: 5000000000 7\nDistance: 6000000000000000000 10",
+        answers: &["1999999998", "10000000034"],
+    }]
+}
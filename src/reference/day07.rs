@@ -0,0 +1,83 @@
+const LABELS: &[u8] = b"23456789TJQKA";
+
+/// The type of a hand without jokers, from 0 (high card) to 6 (five of a kind)
+fn hand_type(hand: &[u8]) -> u32 {
+    let mut counts: Vec<usize> = LABELS
+        .iter()
+        .map(|label| hand.iter().filter(|&c| c == label).count())
+        .filter(|&count| count > 0)
+        .collect();
+    counts.sort_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5] => 6,
+        [4, 1] => 5,
+        [3, 2] => 4,
+        [3, 1, 1] => 3,
+        [2, 2, 1] => 2,
+        [2, 1, 1, 1] => 1,
+        _ => 0,
+    }
+}
+
+/// The best type of a hand, trying every label for every joker
+fn best_hand_type(hand: &mut [u8], index: usize) -> u32 {
+    if index == hand.len() {
+        return hand_type(hand);
+    }
+    if hand[index] != b'J' {
+        return best_hand_type(hand, index + 1);
+    }
+    let best_type = LABELS
+        .iter()
+        .filter(|&&label| label != b'J')
+        .map(|&label| {
+            hand[index] = label;
+            best_hand_type(hand, index + 1)
+        })
+        .max()
+        .unwrap();
+    hand[index] = b'J';
+    best_type
+}
+
+fn total_winnings(hands: &[(&str, u64)], with_jokers: bool) -> u64 {
+    let order: &[u8] = if with_jokers {
+        b"J23456789TQKA"
+    } else {
+        LABELS
+    };
+    let mut keys: Vec<_> = hands
+        .iter()
+        .map(|&(hand, bid)| {
+            let mut cards = hand.as_bytes().to_vec();
+            let hand_type = if with_jokers {
+                best_hand_type(&mut cards, 0)
+            } else {
+                hand_type(&cards)
+            };
+            let ranks: Vec<_> = cards
+                .iter()
+                .map(|card| order.iter().position(|label| label == card).unwrap())
+                .collect();
+            (hand_type, ranks, bid)
+        })
+        .collect();
+    keys.sort();
+    keys.iter()
+        .enumerate()
+        .map(|(index, &(_, _, bid))| (index as u64 + 1) * bid)
+        .sum()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let hands: Vec<(&str, u64)> = input
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line.split_once(' ').unwrap();
+            (hand, bid.parse().unwrap())
+        })
+        .collect();
+    [false, true]
+        .map(|with_jokers| total_winnings(&hands, with_jokers).to_string())
+        .to_vec()
+}
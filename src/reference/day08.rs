@@ -0,0 +1,43 @@
+use crate::utils::FastMap;
+
+fn parse_network(input: &str) -> (&[u8], FastMap<&str, [&str; 2]>) {
+    let (directions, nodes) = input.split_once("\n\n").unwrap();
+    let network = nodes
+        .lines()
+        .map(|line| {
+            let (node, next) = line.split_once(" = ").unwrap();
+            let (left, right) = next[1..next.len() - 1].split_once(", ").unwrap();
+            (node, [left, right])
+        })
+        .collect();
+    (directions.as_bytes(), network)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let (directions, network) = parse_network(input);
+    let next_node = |node: &str, step: usize| {
+        let side = usize::from(directions[step % directions.len()] == b'R');
+        network[node][side]
+    };
+    let mut node = "AAA";
+    let mut num_steps = 0;
+    while node != "ZZZ" {
+        node = next_node(node, num_steps);
+        num_steps += 1;
+    }
+
+    // move all the ghosts together until they are all on a Z node
+    let mut ghosts: Vec<_> = network
+        .keys()
+        .filter(|node| node.ends_with('A'))
+        .copied()
+        .collect();
+    let mut num_ghost_steps = 0;
+    while !ghosts.iter().all(|node| node.ends_with('Z')) {
+        for ghost in &mut ghosts {
+            *ghost = next_node(ghost, num_ghost_steps);
+        }
+        num_ghost_steps += 1;
+    }
+    vec![num_steps.to_string(), num_ghost_steps.to_string()]
+}
@@ -0,0 +1,26 @@
+/// The next and previous values of the sequence, from its table of differences
+fn extrapolate(values: &[i64]) -> (i64, i64) {
+    let mut rows = vec![values.to_vec()];
+    while rows.last().unwrap().iter().any(|&value| value != 0) {
+        let row = rows.last().unwrap();
+        let differences = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        rows.push(differences);
+    }
+    rows.iter().rev().fold((0, 0), |(next, previous), row| {
+        (row[row.len() - 1] + next, row[0] - previous)
+    })
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let (sum_next, sum_previous) = input
+        .lines()
+        .map(|line| {
+            let values: Vec<i64> = line
+                .split_whitespace()
+                .map(|value| value.parse().unwrap())
+                .collect();
+            extrapolate(&values)
+        })
+        .fold((0, 0), |(a, b), (next, previous)| (a + next, b + previous));
+    vec![sum_next.to_string(), sum_previous.to_string()]
+}
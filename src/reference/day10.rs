@@ -0,0 +1,77 @@
+use crate::utils::FastSet;
+
+const NORTH: (i64, i64) = (0, -1);
+const SOUTH: (i64, i64) = (0, 1);
+const WEST: (i64, i64) = (-1, 0);
+const EAST: (i64, i64) = (1, 0);
+
+fn connections(pipe: u8) -> Vec<(i64, i64)> {
+    match pipe {
+        b'|' => vec![NORTH, SOUTH],
+        b'-' => vec![WEST, EAST],
+        b'L' => vec![NORTH, EAST],
+        b'J' => vec![NORTH, WEST],
+        b'7' => vec![SOUTH, WEST],
+        b'F' => vec![SOUTH, EAST],
+        _ => vec![],
+    }
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let pipes: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let pipe_at = |(x, y): (i64, i64)| {
+        let row = pipes.get(usize::try_from(y).ok()?)?;
+        row.get(usize::try_from(x).ok()?).copied()
+    };
+    let start = (0..pipes.len() as i64)
+        .flat_map(|y| (0..pipes[0].len() as i64).map(move |x| (x, y)))
+        .find(|&position| pipe_at(position) == Some(b'S'))
+        .unwrap();
+    // S connects to the neighbors that connect back to it
+    let start_connections: Vec<_> = [NORTH, SOUTH, WEST, EAST]
+        .into_iter()
+        .filter(|&(dx, dy)| {
+            let neighbor = pipe_at((start.0 + dx, start.1 + dy)).unwrap_or(b'.');
+            connections(neighbor).contains(&(-dx, -dy))
+        })
+        .collect();
+
+    let mut main_loop = FastSet::from_iter([start]);
+    let mut position = start;
+    let mut direction = start_connections[0];
+    loop {
+        position = (position.0 + direction.0, position.1 + direction.1);
+        if position == start {
+            break;
+        }
+        main_loop.insert(position);
+        let came_from = (-direction.0, -direction.1);
+        direction = connections(pipe_at(position).unwrap())
+            .into_iter()
+            .find(|&connection| connection != came_from)
+            .unwrap();
+    }
+
+    // cast a ray along every row, crossing the loop at the pipes going north
+    let mut num_tiles_inside = 0;
+    for (y, row) in pipes.iter().enumerate() {
+        let mut is_inside = false;
+        for (x, &pipe) in row.iter().enumerate() {
+            let position = (x as i64, y as i64);
+            if main_loop.contains(&position) {
+                let goes_north = if pipe == b'S' {
+                    start_connections.contains(&NORTH)
+                } else {
+                    connections(pipe).contains(&NORTH)
+                };
+                is_inside ^= goes_north;
+            } else if is_inside {
+                num_tiles_inside += 1;
+            }
+        }
+    }
+    vec![
+        (main_loop.len() / 2).to_string(),
+        num_tiles_inside.to_string(),
+    ]
+}
@@ -0,0 +1,42 @@
+/// The positions of the galaxies once every empty row and column is expanded
+fn expand(galaxies: &[(u64, u64)], expansion_factor: u64) -> Vec<(u64, u64)> {
+    let expand_coordinate = |coordinate: u64, is_x: bool| {
+        let num_empty = (0..coordinate)
+            .filter(|&c| {
+                !galaxies
+                    .iter()
+                    .any(|&(x, y)| if is_x { x == c } else { y == c })
+            })
+            .count() as u64;
+        coordinate + num_empty * (expansion_factor - 1)
+    };
+    galaxies
+        .iter()
+        .map(|&(x, y)| (expand_coordinate(x, true), expand_coordinate(y, false)))
+        .collect()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let galaxies: Vec<(u64, u64)> = input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, c)| c == b'#')
+                .map(move |(x, _)| (x as u64, y as u64))
+        })
+        .collect();
+    [2, 1000000]
+        .map(|expansion_factor| {
+            let galaxies = expand(&galaxies, expansion_factor);
+            let mut total_distance = 0;
+            for (i, &(x1, y1)) in galaxies.iter().enumerate() {
+                for &(x2, y2) in &galaxies[i + 1..] {
+                    total_distance += x1.abs_diff(x2) + y1.abs_diff(y2);
+                }
+            }
+            total_distance.to_string()
+        })
+        .to_vec()
+}
@@ -0,0 +1,77 @@
+fn group_lengths(springs: &[u8]) -> Vec<usize> {
+    springs
+        .split(|&c| c == b'.')
+        .filter(|group| !group.is_empty())
+        .map(|group| group.len())
+        .collect()
+}
+
+/// Tries every assignment of the unknown springs
+fn count_assignments(springs: &mut [u8], index: usize, groups: &[usize]) -> u64 {
+    match springs[index..].iter().position(|&c| c == b'?') {
+        None => u64::from(group_lengths(springs) == groups),
+        Some(offset) => {
+            let unknown = index + offset;
+            let mut count = 0;
+            for state in [b'.', b'#'] {
+                springs[unknown] = state;
+                count += count_assignments(springs, unknown + 1, groups);
+            }
+            springs[unknown] = b'?';
+            count
+        }
+    }
+}
+
+/// Counts the arrangements with a table of the number of ways to place the first groups in the
+/// first springs, the next spring being operational
+fn count_arrangements(springs: &[u8], groups: &[usize]) -> u64 {
+    // ways[i][g]: arrangements of springs[..i] with g complete groups, springs[i] not damaged
+    let n = springs.len();
+    let mut ways = vec![vec![0u64; groups.len() + 1]; n + 2];
+    ways[0][0] = 1;
+    for i in 0..=n {
+        for g in 0..=groups.len() {
+            let count = ways[i][g];
+            if count == 0 || i == n {
+                continue;
+            }
+            if springs[i] != b'#' {
+                ways[i + 1][g] += count;
+            }
+            if let Some(&length) = groups.get(g) {
+                let end = i + length;
+                if end <= n && !springs[i..end].contains(&b'.') && springs.get(end) != Some(&b'#') {
+                    ways[(end + 1).min(n + 1)][g + 1] += count;
+                }
+            }
+        }
+    }
+    ways[n][groups.len()] + ways[n + 1][groups.len()]
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let records: Vec<(&str, Vec<usize>)> = input
+        .lines()
+        .map(|line| {
+            let (springs, groups) = line.split_once(' ').unwrap();
+            let groups = groups.split(',').map(|n| n.parse().unwrap()).collect();
+            (springs, groups)
+        })
+        .collect();
+    let num_arrangements: u64 = records
+        .iter()
+        .map(|(springs, groups)| count_assignments(&mut springs.as_bytes().to_vec(), 0, groups))
+        .sum();
+    let num_unfolded_arrangements: u64 = records
+        .iter()
+        .map(|(springs, groups)| {
+            let springs = [*springs; 5].join("?");
+            count_arrangements(springs.as_bytes(), &groups.repeat(5))
+        })
+        .sum();
+    vec![
+        num_arrangements.to_string(),
+        num_unfolded_arrangements.to_string(),
+    ]
+}
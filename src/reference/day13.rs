@@ -0,0 +1,44 @@
+/// The summaries of the perfect reflection lines of the pattern: columns to the left of a
+/// vertical line, or 100 times the rows above a horizontal one, vertical lines first
+fn reflection_lines(pattern: &[Vec<u8>]) -> Vec<usize> {
+    let height = pattern.len();
+    let width = pattern[0].len();
+    let vertical = (1..width).filter(|&x| {
+        pattern
+            .iter()
+            .all(|row| (0..x.min(width - x)).all(|offset| row[x - 1 - offset] == row[x + offset]))
+    });
+    let horizontal = (1..height)
+        .filter(|&y| {
+            (0..y.min(height - y)).all(|offset| pattern[y - 1 - offset] == pattern[y + offset])
+        })
+        .map(|y| 100 * y);
+    vertical.chain(horizontal).collect()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut total = 0;
+    let mut total_with_smudge = 0;
+    for pattern in input.split("\n\n") {
+        let mut pattern: Vec<Vec<u8>> =
+            pattern.lines().map(|line| line.bytes().collect()).collect();
+        let line = reflection_lines(&pattern)[0];
+        total += line;
+        // clean every cell, until a different reflection line appears
+        let mut new_lines = vec![];
+        for y in 0..pattern.len() {
+            for x in 0..pattern[0].len() {
+                let cell = pattern[y][x];
+                pattern[y][x] = if cell == b'#' { b'.' } else { b'#' };
+                new_lines.extend(
+                    reflection_lines(&pattern)
+                        .into_iter()
+                        .filter(|&l| l != line),
+                );
+                pattern[y][x] = cell;
+            }
+        }
+        total_with_smudge += new_lines.iter().min_by_key(|&&l| (l >= 100, l)).unwrap();
+    }
+    vec![total.to_string(), total_with_smudge.to_string()]
+}
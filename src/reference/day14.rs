@@ -0,0 +1,58 @@
+use crate::utils::FastMap;
+
+/// Rolls the rocks one by one, the ones closest to the edge first, as far as they can go
+fn tilt(platform: &mut [Vec<u8>], dx: i64, dy: i64) {
+    let height = platform.len() as i64;
+    let width = platform[0].len() as i64;
+    let mut cells: Vec<_> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .collect();
+    cells.sort_by_key(|&(x, y)| -(x * dx + y * dy));
+    for (mut x, mut y) in cells {
+        if platform[y as usize][x as usize] != b'O' {
+            continue;
+        }
+        while (0..width).contains(&(x + dx))
+            && (0..height).contains(&(y + dy))
+            && platform[(y + dy) as usize][(x + dx) as usize] == b'.'
+        {
+            platform[y as usize][x as usize] = b'.';
+            (x, y) = (x + dx, y + dy);
+            platform[y as usize][x as usize] = b'O';
+        }
+    }
+}
+
+fn total_load(platform: &[Vec<u8>]) -> usize {
+    platform
+        .iter()
+        .enumerate()
+        .map(|(y, row)| row.iter().filter(|&&c| c == b'O').count() * (platform.len() - y))
+        .sum()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut platform: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+    let mut tilted = platform.clone();
+    tilt(&mut tilted, 0, -1);
+    let load = total_load(&tilted);
+
+    let num_cycles = 1_000_000_000;
+    let mut seen = FastMap::default();
+    let mut index = 0;
+    while index < num_cycles {
+        if let Some(previous_index) = seen.insert(platform.clone(), index) {
+            let period = index - previous_index;
+            index += (num_cycles - index) / period * period;
+            seen.clear();
+            if index == num_cycles {
+                break;
+            }
+        }
+        for (dx, dy) in [(0, -1), (-1, 0), (0, 1), (1, 0)] {
+            tilt(&mut platform, dx, dy);
+        }
+        index += 1;
+    }
+    vec![load.to_string(), total_load(&platform).to_string()]
+}
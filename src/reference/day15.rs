@@ -0,0 +1,37 @@
+fn hash(text: &str) -> usize {
+    text.bytes()
+        .fold(0, |value, c| (value + c as usize) * 17 % 256)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let steps: Vec<_> = input.trim_end().split(',').collect();
+    let sum_of_hashes: usize = steps.iter().map(|step| hash(step)).sum();
+
+    // all the lenses in insertion order, their slot is their rank in their box
+    let mut lenses: Vec<(&str, usize)> = vec![];
+    for step in &steps {
+        if let Some(label) = step.strip_suffix('-') {
+            lenses.retain(|&(other_label, _)| other_label != label);
+        } else {
+            let (label, focal_length) = step.split_once('=').unwrap();
+            let focal_length = focal_length.parse().unwrap();
+            match lenses
+                .iter_mut()
+                .find(|(other_label, _)| *other_label == label)
+            {
+                Some(lens) => lens.1 = focal_length,
+                None => lenses.push((label, focal_length)),
+            }
+        }
+    }
+    let mut num_lenses_in_box = [0; 256];
+    let focusing_power: usize = lenses
+        .iter()
+        .map(|&(label, focal_length)| {
+            let box_number = hash(label);
+            num_lenses_in_box[box_number] += 1;
+            (box_number + 1) * num_lenses_in_box[box_number] * focal_length
+        })
+        .sum();
+    vec![sum_of_hashes.to_string(), focusing_power.to_string()]
+}
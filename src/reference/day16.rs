@@ -0,0 +1,45 @@
+use crate::utils::FastSet;
+
+/// Follows every beam through a queue of (position, direction) states
+fn count_energized(grid: &[&[u8]], start: (i64, i64, i64, i64)) -> usize {
+    let height = grid.len() as i64;
+    let width = grid[0].len() as i64;
+    let mut seen = FastSet::default();
+    let mut beams = vec![start];
+    while let Some((x, y, dx, dy)) = beams.pop() {
+        if !(0..width).contains(&x) || !(0..height).contains(&y) || !seen.insert((x, y, dx, dy)) {
+            continue;
+        }
+        let directions = match grid[y as usize][x as usize] {
+            b'/' => vec![(-dy, -dx)],
+            b'\\' => vec![(dy, dx)],
+            b'|' if dx != 0 => vec![(0, -1), (0, 1)],
+            b'-' if dy != 0 => vec![(-1, 0), (1, 0)],
+            _ => vec![(dx, dy)],
+        };
+        beams.extend(
+            directions
+                .into_iter()
+                .map(|(dx, dy)| (x + dx, y + dy, dx, dy)),
+        );
+    }
+    seen.iter()
+        .map(|&(x, y, _, _)| (x, y))
+        .collect::<FastSet<_>>()
+        .len()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let grid: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let height = grid.len() as i64;
+    let width = grid[0].len() as i64;
+    let num_energized = count_energized(&grid, (0, 0, 1, 0));
+    let entry_points = (0..width)
+        .flat_map(|x| [(x, 0, 0, 1), (x, height - 1, 0, -1)])
+        .chain((0..height).flat_map(|y| [(0, y, 1, 0), (width - 1, y, -1, 0)]));
+    let max_energized = entry_points
+        .map(|start| count_energized(&grid, start))
+        .max()
+        .unwrap();
+    vec![num_energized.to_string(), max_energized.to_string()]
+}
@@ -0,0 +1,47 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::utils::FastSet;
+
+const DIRECTIONS: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Dijkstra's algorithm, one block at a time, on (position, direction, blocks in a straight
+/// line) states
+fn min_heat_loss(grid: &[&[u8]], min_straight: usize, max_straight: usize) -> u64 {
+    let height = grid.len() as i64;
+    let width = grid[0].len() as i64;
+    let mut queue =
+        BinaryHeap::from([Reverse((0, 0, 0, 0usize, 0usize)), Reverse((0, 0, 0, 1, 0))]);
+    let mut visited = FastSet::default();
+    while let Some(Reverse((heat_loss, x, y, direction, num_straight))) = queue.pop() {
+        if (x, y) == (width - 1, height - 1) && num_straight >= min_straight {
+            return heat_loss;
+        }
+        if !visited.insert((x, y, direction, num_straight)) {
+            continue;
+        }
+        for turn in [0, 1, 3] {
+            let new_direction = (direction + turn) % 4;
+            let new_num_straight = if turn == 0 { num_straight + 1 } else { 1 };
+            let is_turn_allowed = turn == 0 || num_straight >= min_straight || num_straight == 0;
+            if !is_turn_allowed || new_num_straight > max_straight {
+                continue;
+            }
+            let (dx, dy) = DIRECTIONS[new_direction];
+            let (x, y) = (x + dx, y + dy);
+            if (0..width).contains(&x) && (0..height).contains(&y) {
+                let heat_loss = heat_loss + (grid[y as usize][x as usize] - b'0') as u64;
+                queue.push(Reverse((heat_loss, x, y, new_direction, new_num_straight)));
+            }
+        }
+    }
+    unreachable!()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let grid: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    vec![
+        min_heat_loss(&grid, 1, 3).to_string(),
+        min_heat_loss(&grid, 4, 10).to_string(),
+    ]
+}
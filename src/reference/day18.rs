@@ -0,0 +1,88 @@
+/// Digs the trench on a coordinate-compressed grid, then floods the outside of the lagoon
+fn lagoon_volume(dig_plan: &[((i64, i64), i64)]) -> i64 {
+    let mut corners = vec![(0, 0)];
+    for &((dx, dy), length) in dig_plan {
+        let (x, y) = *corners.last().unwrap();
+        corners.push((x + dx * length, y + dy * length));
+    }
+    // compressed cell i covers the unit cells from bounds[i] to bounds[i + 1] excluded
+    let compress = |coordinates: Vec<i64>| {
+        let min = *coordinates.iter().min().unwrap();
+        let max = *coordinates.iter().max().unwrap();
+        let mut bounds: Vec<_> = coordinates
+            .iter()
+            .flat_map(|&c| [c, c + 1])
+            .chain([min - 1, max + 2])
+            .collect();
+        bounds.sort();
+        bounds.dedup();
+        bounds
+    };
+    let x_bounds = compress(corners.iter().map(|&(x, _)| x).collect());
+    let y_bounds = compress(corners.iter().map(|&(_, y)| y).collect());
+    let (width, height) = (x_bounds.len() - 1, y_bounds.len() - 1);
+    let index = |bounds: &[i64], c: i64| bounds.iter().position(|&b| b == c).unwrap();
+
+    let mut is_trench = vec![vec![false; width]; height];
+    for pair in corners.windows(2) {
+        let ((xa, ya), (xb, yb)) = (pair[0], pair[1]);
+        for row in &mut is_trench[index(&y_bounds, ya.min(yb))..index(&y_bounds, ya.max(yb) + 1)] {
+            row[index(&x_bounds, xa.min(xb))..index(&x_bounds, xa.max(xb) + 1)].fill(true);
+        }
+    }
+    let mut is_outside = vec![vec![false; width]; height];
+    let mut to_visit = vec![(0, 0)];
+    while let Some((x, y)) = to_visit.pop() {
+        if is_trench[y][x] || is_outside[y][x] {
+            continue;
+        }
+        is_outside[y][x] = true;
+        if x > 0 {
+            to_visit.push((x - 1, y));
+        }
+        if y > 0 {
+            to_visit.push((x, y - 1));
+        }
+        if x + 1 < width {
+            to_visit.push((x + 1, y));
+        }
+        if y + 1 < height {
+            to_visit.push((x, y + 1));
+        }
+    }
+    let mut volume = 0;
+    for y in 0..height {
+        for x in 0..width {
+            if !is_outside[y][x] {
+                volume += (x_bounds[x + 1] - x_bounds[x]) * (y_bounds[y + 1] - y_bounds[y]);
+            }
+        }
+    }
+    volume
+}
+
+fn direction_step(direction: char) -> (i64, i64) {
+    match direction {
+        'R' | '0' => (1, 0),
+        'D' | '1' => (0, 1),
+        'L' | '2' => (-1, 0),
+        _ => (0, -1),
+    }
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut dig_plan = vec![];
+    let mut color_dig_plan = vec![];
+    for line in input.lines() {
+        let parts: Vec<_> = line.split(' ').collect();
+        let direction = parts[0].chars().next().unwrap();
+        dig_plan.push((direction_step(direction), parts[1].parse().unwrap()));
+        let color = parts[2].trim_matches(|c| "(#)".contains(c));
+        let length = i64::from_str_radix(&color[..5], 16).unwrap();
+        color_dig_plan.push((direction_step(color.chars().nth(5).unwrap()), length));
+    }
+    vec![
+        lagoon_volume(&dig_plan).to_string(),
+        lagoon_volume(&color_dig_plan).to_string(),
+    ]
+}
@@ -0,0 +1,71 @@
+use crate::utils::FastMap;
+
+/// A rule as (category, comparison, value, action), the comparison being None for the last
+/// rule of a workflow
+type Rule<'a> = (usize, Option<u8>, u64, &'a str);
+
+fn parse_workflow(line: &str) -> (&str, Vec<Rule<'_>>) {
+    let (name, rules) = line[..line.len() - 1].split_once('{').unwrap();
+    let rules = rules
+        .split(',')
+        .map(|rule| match rule.split_once(':') {
+            Some((condition, action)) => {
+                let category = "xmas".find(&condition[..1]).unwrap();
+                let value = condition[2..].parse().unwrap();
+                (category, Some(condition.as_bytes()[1]), value, action)
+            }
+            None => (0, None, 0, rule),
+        })
+        .collect();
+    (name, rules)
+}
+
+/// Sends the ranges of ratings (inclusive) through the workflows, splitting them at every
+/// rule, and counts the accepted combinations
+fn count_accepted(
+    workflows: &FastMap<&str, Vec<Rule>>,
+    name: &str,
+    mut ranges: [(u64, u64); 4],
+) -> u64 {
+    if name == "R" || ranges.iter().any(|&(low, high)| low > high) {
+        return 0;
+    }
+    if name == "A" {
+        return ranges.iter().map(|&(low, high)| high - low + 1).product();
+    }
+    let mut count = 0;
+    for &(category, comparison, value, action) in &workflows[name] {
+        let (low, high) = ranges[category];
+        let (matching, rest) = match comparison {
+            None => ((low, high), (1, 0)),
+            Some(b'<') => ((low, high.min(value - 1)), (low.max(value), high)),
+            Some(_) => ((low.max(value + 1), high), (low, high.min(value))),
+        };
+        let mut matching_ranges = ranges;
+        matching_ranges[category] = matching;
+        count += count_accepted(workflows, action, matching_ranges);
+        ranges[category] = rest;
+    }
+    count
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let (workflows, parts) = input.split_once("\n\n").unwrap();
+    let workflows: FastMap<_, _> = workflows.lines().map(parse_workflow).collect();
+    let mut sum_accepted_ratings = 0;
+    for part in parts.lines() {
+        let ratings: Vec<u64> = part[1..part.len() - 1]
+            .split(',')
+            .map(|rating| rating[2..].parse().unwrap())
+            .collect();
+        let single_ranges = [0, 1, 2, 3].map(|i| (ratings[i], ratings[i]));
+        if count_accepted(&workflows, "in", single_ranges) == 1 {
+            sum_accepted_ratings += ratings.iter().sum::<u64>();
+        }
+    }
+    let num_combinations = count_accepted(&workflows, "in", [(1, 4000); 4]);
+    vec![
+        sum_accepted_ratings.to_string(),
+        num_combinations.to_string(),
+    ]
+}
@@ -0,0 +1,90 @@
+use crate::utils::FastMap;
+
+struct Network<'a> {
+    kinds: FastMap<&'a str, u8>,
+    destinations: FastMap<&'a str, Vec<&'a str>>,
+    is_on: FastMap<&'a str, bool>,
+    last_pulses: FastMap<&'a str, FastMap<&'a str, bool>>,
+}
+
+impl<'a> Network<'a> {
+    fn parse(input: &'a str) -> Self {
+        let mut network = Self {
+            kinds: FastMap::default(),
+            destinations: FastMap::default(),
+            is_on: FastMap::default(),
+            last_pulses: FastMap::default(),
+        };
+        for line in input.lines() {
+            let (module, destinations) = line.split_once(" -> ").unwrap();
+            let (kind, name) = match module.as_bytes()[0] {
+                kind @ (b'%' | b'&') => (kind, &module[1..]),
+                _ => (b'b', module),
+            };
+            network.kinds.insert(name, kind);
+            network
+                .destinations
+                .insert(name, destinations.split(", ").collect());
+        }
+        for (&name, destinations) in &network.destinations {
+            for &destination in destinations {
+                network
+                    .last_pulses
+                    .entry(destination)
+                    .or_default()
+                    .insert(name, false);
+            }
+        }
+        network
+    }
+
+    /// Pushes the button, and returns the number of low and high pulses, and whether rx
+    /// received a low pulse
+    fn push_button(&mut self) -> (u64, u64, bool) {
+        let (mut num_low, mut num_high, mut is_rx_started) = (0, 0, false);
+        let mut pulses = std::collections::VecDeque::from([("button", "broadcaster", false)]);
+        while let Some((from, to, is_high)) = pulses.pop_front() {
+            if is_high {
+                num_high += 1;
+            } else {
+                num_low += 1;
+                is_rx_started |= to == "rx";
+            }
+            let output = match self.kinds.get(to) {
+                Some(b'%') if !is_high => {
+                    let is_on = self.is_on.entry(to).or_default();
+                    *is_on = !*is_on;
+                    *is_on
+                }
+                Some(b'&') => {
+                    let last_pulses = self.last_pulses.get_mut(to).unwrap();
+                    last_pulses.insert(from, is_high);
+                    !last_pulses.values().all(|&is_high| is_high)
+                }
+                Some(b'b') => is_high,
+                _ => continue,
+            };
+            for &destination in &self.destinations[to] {
+                pulses.push_back((to, destination, output));
+            }
+        }
+        (num_low, num_high, is_rx_started)
+    }
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut network = Network::parse(input);
+    let (mut total_low, mut total_high) = (0, 0);
+    for _ in 0..1000 {
+        let (num_low, num_high, _) = network.push_button();
+        total_low += num_low;
+        total_high += num_high;
+    }
+    // push the button until rx receives a low pulse
+    let mut network = Network::parse(input);
+    let mut num_pushes = 1;
+    while !network.push_button().2 {
+        num_pushes += 1;
+    }
+    vec![(total_low * total_high).to_string(), num_pushes.to_string()]
+}
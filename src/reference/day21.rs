@@ -0,0 +1,61 @@
+use crate::utils::FastMap;
+
+use std::collections::VecDeque;
+
+/// Counts the plots reachable in exactly num_steps steps, from the distances of every plot
+/// found with a breadth-first search
+fn count_reachable_plots(garden: &[&[u8]], num_steps: u64, is_infinite: bool) -> u64 {
+    let height = garden.len() as i64;
+    let width = garden[0].len() as i64;
+    let start = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .find(|&(x, y)| garden[y as usize][x as usize] == b'S')
+        .unwrap();
+    let is_plot = |x: i64, y: i64| {
+        let is_inside = (0..width).contains(&x) && (0..height).contains(&y);
+        if !is_infinite && !is_inside {
+            return false;
+        }
+        garden[y.rem_euclid(height) as usize][x.rem_euclid(width) as usize] != b'#'
+    };
+    let mut distances = FastMap::from_iter([(start, 0)]);
+    let mut to_visit = VecDeque::from([start]);
+    while let Some((x, y)) = to_visit.pop_front() {
+        let distance = distances[&(x, y)];
+        if distance == num_steps {
+            continue;
+        }
+        for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if is_plot(nx, ny) && !distances.contains_key(&(nx, ny)) {
+                distances.insert((nx, ny), distance + 1);
+                to_visit.push_back((nx, ny));
+            }
+        }
+    }
+    distances
+        .values()
+        .filter(|&&distance| distance % 2 == num_steps % 2)
+        .count() as u64
+}
+
+/// The step counts checked for a garden: a full walk for part 1, and for part 2 a walk
+/// ending on the edge of a garden like the real 26501365 steps, but after 8 gardens
+fn num_steps(input: &str) -> (u64, u64) {
+    let size = input.lines().count() as u64;
+    (size, 8 * size + size / 2)
+}
+
+/// The optimized solver, with the step counts of num_steps
+pub fn fast_solve(input: &str) -> Vec<String> {
+    let (part1_steps, part2_steps) = num_steps(input);
+    crate::day21::solve_with_steps(input, part1_steps, part2_steps)
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let garden: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let (part1_steps, part2_steps) = num_steps(input);
+    vec![
+        count_reachable_plots(&garden, part1_steps, false).to_string(),
+        count_reachable_plots(&garden, part2_steps, true).to_string(),
+    ]
+}
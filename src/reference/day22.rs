@@ -0,0 +1,65 @@
+use crate::utils::FastSet;
+
+type Brick = [[i64; 3]; 2];
+
+fn cubes(brick: &Brick) -> impl Iterator<Item = [i64; 3]> + '_ {
+    let [start, end] = brick;
+    (start[0]..=end[0]).flat_map(move |x| {
+        (start[1]..=end[1]).flat_map(move |y| (start[2]..=end[2]).map(move |z| [x, y, z]))
+    })
+}
+
+/// Lowers the bricks one level at a time until none can fall, and returns how many fell
+fn settle(bricks: &mut [Brick]) -> usize {
+    let mut occupied: FastSet<_> = bricks.iter().flat_map(cubes).collect();
+    let mut has_fallen = vec![false; bricks.len()];
+    let mut is_moving = true;
+    while is_moving {
+        is_moving = false;
+        for (brick, has_fallen) in bricks.iter_mut().zip(&mut has_fallen) {
+            let can_fall = brick[0][2] > 1
+                && cubes(brick).all(|[x, y, z]| {
+                    !occupied.contains(&[x, y, z - 1]) || cubes(brick).any(|c| c == [x, y, z - 1])
+                });
+            if can_fall {
+                for cube in cubes(brick) {
+                    occupied.remove(&cube);
+                }
+                brick[0][2] -= 1;
+                brick[1][2] -= 1;
+                occupied.extend(cubes(brick));
+                *has_fallen = true;
+                is_moving = true;
+            }
+        }
+    }
+    has_fallen.iter().filter(|&&has_fallen| has_fallen).count()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let mut bricks: Vec<Brick> = input
+        .lines()
+        .map(|line| {
+            let (start, end) = line.split_once('~').unwrap();
+            let parse = |corner: &str| {
+                let coordinates: Vec<i64> = corner.split(',').map(|c| c.parse().unwrap()).collect();
+                [coordinates[0], coordinates[1], coordinates[2]]
+            };
+            [parse(start), parse(end)]
+        })
+        .collect();
+    settle(&mut bricks);
+    // disintegrate every brick in turn, and let the others settle again
+    let mut num_safe = 0;
+    let mut total_fallen = 0;
+    for index in 0..bricks.len() {
+        let mut others = bricks.clone();
+        others.remove(index);
+        let num_fallen = settle(&mut others);
+        if num_fallen == 0 {
+            num_safe += 1;
+        }
+        total_fallen += num_fallen;
+    }
+    vec![num_safe.to_string(), total_fallen.to_string()]
+}
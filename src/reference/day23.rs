@@ -0,0 +1,44 @@
+/// The longest path to the end from (x, y), cell by cell, without visiting a cell twice
+fn longest_hike(
+    grid: &[&[u8]],
+    (x, y): (usize, usize),
+    is_visited: &mut [Vec<bool>],
+    is_slippery: bool,
+) -> Option<usize> {
+    if y == grid.len() - 1 {
+        return Some(0);
+    }
+    is_visited[y][x] = true;
+    let directions: &[(isize, isize)] = match grid[y][x] {
+        b'>' if is_slippery => &[(1, 0)],
+        b'<' if is_slippery => &[(-1, 0)],
+        b'v' if is_slippery => &[(0, 1)],
+        b'^' if is_slippery => &[(0, -1)],
+        _ => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+    };
+    let mut longest = None;
+    for &(dx, dy) in directions {
+        let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+            continue;
+        };
+        if ny < grid.len() && nx < grid[0].len() && grid[ny][nx] != b'#' && !is_visited[ny][nx] {
+            let length = longest_hike(grid, (nx, ny), is_visited, is_slippery);
+            longest = longest.max(length.map(|length| length + 1));
+        }
+    }
+    is_visited[y][x] = false;
+    longest
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let grid: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let start_x = grid[0].iter().position(|&c| c == b'.').unwrap();
+    [true, false]
+        .map(|is_slippery| {
+            let mut is_visited = vec![vec![false; grid[0].len()]; grid.len()];
+            longest_hike(&grid, (start_x, 0), &mut is_visited, is_slippery)
+                .unwrap()
+                .to_string()
+        })
+        .to_vec()
+}
@@ -0,0 +1,97 @@
+const MIN: i128 = 200000000000000;
+const MAX: i128 = 400000000000000;
+
+/// Whether the paths of two hailstones cross in the future inside the test area, with exact
+/// integer arithmetic (Cramer's rule with the denominator kept apart)
+fn are_crossing_in_area(h1: &[i128], h2: &[i128]) -> bool {
+    let det = h2[3] * h1[4] - h1[3] * h2[4];
+    if det == 0 {
+        return false;
+    }
+    let (dx, dy) = (h2[0] - h1[0], h2[1] - h1[1]);
+    let (mut t1, mut t2, mut det) = (h2[3] * dy - h2[4] * dx, h1[3] * dy - h1[4] * dx, det);
+    if det < 0 {
+        (t1, t2, det) = (-t1, -t2, -det);
+    }
+    // the crossing point is h1 + t1 / det * v1
+    let area = MIN * det..=MAX * det;
+    t1 >= 0
+        && t2 >= 0
+        && area.contains(&(h1[0] * det + h1[3] * t1))
+        && area.contains(&(h1[1] * det + h1[4] * t1))
+}
+
+/// The time (if any) at which a hailstone is at the position of the rock, in the x-y plane
+fn hit_time(h: &[i128], position: [i128; 2], velocity: [i128; 2]) -> Option<i128> {
+    let mut time = None;
+    for i in 0..2 {
+        let (gap, speed) = (h[i] - position[i], velocity[i] - h[i + 3]);
+        if speed == 0 {
+            if gap != 0 {
+                return None;
+            }
+        } else if gap % speed != 0 || time.is_some_and(|time| time != gap / speed) {
+            return None;
+        } else {
+            time = Some(gap / speed);
+        }
+    }
+    time
+}
+
+/// Tries every rock velocity in the x-y plane: the rock position follows from the first two
+/// hailstones, and must be hit by all of them
+fn find_rock(hailstones: &[Vec<i128>]) -> i128 {
+    let (h0, h1) = (&hailstones[0], &hailstones[1]);
+    for vx in -300..=300 {
+        for vy in -300..=300 {
+            // h0 + u0 * t0 = h1 + u1 * t1 with the velocities u relative to the rock
+            let (u0, u1) = ([h0[3] - vx, h0[4] - vy], [h1[3] - vx, h1[4] - vy]);
+            let det = u1[0] * u0[1] - u0[0] * u1[1];
+            if det == 0 {
+                continue;
+            }
+            let t0_det = u1[0] * (h1[1] - h0[1]) - u1[1] * (h1[0] - h0[0]);
+            if t0_det % det != 0 {
+                continue;
+            }
+            let t0 = t0_det / det;
+            let position = [h0[0] + u0[0] * t0, h0[1] + u0[1] * t0];
+            let Some(times) = hailstones
+                .iter()
+                .map(|h| hit_time(h, position, [vx, vy]))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            if times[0] == times[1] {
+                continue;
+            }
+            let z = |i: usize| hailstones[i][2] + hailstones[i][5] * times[i];
+            let vz = (z(0) - z(1)) / (times[0] - times[1]);
+            let z0 = z(0) - vz * times[0];
+            if (0..hailstones.len()).all(|i| z(i) == z0 + vz * times[i]) {
+                return position[0] + position[1] + z0;
+            }
+        }
+    }
+    unreachable!()
+}
+
+pub fn solve(input: &str) -> Vec<String> {
+    let hailstones: Vec<Vec<i128>> = input
+        .lines()
+        .map(|line| {
+            line.split([',', '@'])
+                .map(|value| value.trim().parse().unwrap())
+                .collect()
+        })
+        .collect();
+    let mut num_crossing = 0;
+    for (i, h1) in hailstones.iter().enumerate() {
+        for h2 in &hailstones[i + 1..] {
+            num_crossing += usize::from(are_crossing_in_area(h1, h2));
+        }
+    }
+    vec![num_crossing.to_string(), find_rock(&hailstones).to_string()]
+}
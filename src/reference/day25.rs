@@ -0,0 +1,49 @@
+use crate::utils::FastMap;
+
+/// The size of the group of the first component once the removed wires are cut
+fn group_size(num_components: usize, wires: &[(usize, usize)], removed: &[usize]) -> usize {
+    let mut neighbors = vec![vec![]; num_components];
+    for (index, &(a, b)) in wires.iter().enumerate() {
+        if !removed.contains(&index) {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+    let mut is_visited = vec![false; num_components];
+    let mut to_visit = vec![0];
+    while let Some(component) = to_visit.pop() {
+        if !is_visited[component] {
+            is_visited[component] = true;
+            to_visit.extend(&neighbors[component]);
+        }
+    }
+    is_visited.iter().filter(|&&is_visited| is_visited).count()
+}
+
+/// Tries to cut every triple of wires until the components are split in two
+pub fn solve(input: &str) -> Vec<String> {
+    let mut indices = FastMap::default();
+    let mut wires = vec![];
+    for line in input.lines() {
+        let (from, destinations) = line.split_once(": ").unwrap();
+        for to in destinations.split(' ') {
+            let num_indices = indices.len();
+            let from = *indices.entry(from).or_insert(num_indices);
+            let num_indices = indices.len();
+            let to = *indices.entry(to).or_insert(num_indices);
+            wires.push((from, to));
+        }
+    }
+    let n = indices.len();
+    for i in 0..wires.len() {
+        for j in i + 1..wires.len() {
+            for k in j + 1..wires.len() {
+                let size = group_size(n, &wires, &[i, j, k]);
+                if size < n {
+                    return vec![(size * (n - size)).to_string()];
+                }
+            }
+        }
+    }
+    unreachable!()
+}
@@ -1,15 +1,33 @@
 use itertools::Itertools;
 use num::{Integer, Num};
 
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
+pub mod aho_corasick;
+pub mod bitgrid;
+pub mod dot;
+pub mod fast_hash;
+pub mod graph;
+pub mod grid;
+pub mod image;
+pub mod ints;
+pub mod linalg;
+pub mod memo;
+pub mod parse;
+pub mod poly;
+pub mod polygon;
+pub mod rng;
+pub mod union_find;
+pub mod viz;
+
+pub use bitgrid::BitGrid;
+pub use fast_hash::{FastMap, FastSet, FxHasher};
+pub use grid::Grid;
+pub use ints::{ints, parse_n};
+pub use union_find::{RollbackUnionFind, UnionFind};
+
 pub fn parse_ints<T: Num + FromStr>(input: &str, signed: bool) -> Vec<T> {
-    input
-        .split(|c: char| !(c.is_ascii_digit() || (signed && c == '-')))
-        .filter_map(|s| s.parse().ok())
-        .collect()
+    ints(input, signed).filter_map(Result::ok).collect()
 }
 
 pub fn parse_int_vecs<T: Num + FromStr>(input: &str, signed: bool) -> Vec<Vec<T>> {
@@ -19,14 +37,6 @@ pub fn parse_int_vecs<T: Num + FromStr>(input: &str, signed: bool) -> Vec<Vec<T>
         .collect_vec()
 }
 
-pub fn argmax<T: Ord>(array: &[T]) -> Option<usize> {
-    array
-        .iter()
-        .enumerate()
-        .max_by_key(|&(_, count)| count)
-        .map(|(index, _)| index)
-}
-
 pub fn gcd<T: Integer + Copy>(mut a: T, mut b: T) -> T {
     while b != T::zero() {
         let t = b;
@@ -39,9 +49,3 @@ pub fn gcd<T: Integer + Copy>(mut a: T, mut b: T) -> T {
 pub fn lcm<T: Integer + Copy>(a: T, b: T) -> T {
     a / gcd(a, b) * b
 }
-
-pub fn compute_hash<T: Hash>(t: &T) -> u64 {
-    let mut s = DefaultHasher::new();
-    t.hash(&mut s);
-    s.finish()
-}
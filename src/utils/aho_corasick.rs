@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+/// An occurrence of a pattern: its index, and its position in the text (end excluded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An Aho-Corasick automaton, which finds all the occurrences of several patterns in a text in
+/// a single pass, overlapping ones included
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// The next state for every state and byte, with the failure links already followed
+    transitions: Vec<[u32; 256]>,
+    /// The patterns ending at every state, the longest first
+    outputs: Vec<Vec<usize>>,
+    pattern_lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        const NONE: u32 = u32::MAX;
+        let mut transitions = vec![[NONE; 256]];
+        let mut outputs = vec![vec![]];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut state = 0;
+            for &byte in pattern.as_ref() {
+                if transitions[state][byte as usize] == NONE {
+                    transitions[state][byte as usize] = transitions.len() as u32;
+                    transitions.push([NONE; 256]);
+                    outputs.push(vec![]);
+                }
+                state = transitions[state][byte as usize] as usize;
+            }
+            outputs[state].push(index);
+        }
+
+        // breadth-first, so that the failure state of every state is complete before it
+        let mut failures = vec![0; transitions.len()];
+        let mut to_visit = VecDeque::from([0]);
+        while let Some(state) = to_visit.pop_front() {
+            let fallbacks = if state == 0 {
+                [0; 256]
+            } else {
+                transitions[failures[state]]
+            };
+            for (next, fallback) in transitions[state].iter_mut().zip(fallbacks) {
+                if *next == NONE {
+                    *next = fallback;
+                } else {
+                    let next = *next as usize;
+                    failures[next] = fallback as usize;
+                    let inherited = outputs[failures[next]].clone();
+                    outputs[next].extend(inherited);
+                    to_visit.push_back(next);
+                }
+            }
+        }
+        Self {
+            transitions,
+            outputs,
+            pattern_lengths: patterns.iter().map(|p| p.as_ref().len()).collect(),
+        }
+    }
+
+    pub fn num_patterns(&self) -> usize {
+        self.pattern_lengths.len()
+    }
+
+    pub fn max_pattern_len(&self) -> usize {
+        self.pattern_lengths.iter().copied().max().unwrap_or(0)
+    }
+
+    /// All the occurrences in the text, overlapping ones included, by end position (and the
+    /// longest first for the same end)
+    pub fn find_overlapping<'a>(
+        &'a self,
+        text: impl IntoIterator<Item = u8> + 'a,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        text.into_iter().enumerate().flat_map(move |(index, byte)| {
+            state = self.transitions[state][byte as usize] as usize;
+            self.outputs[state].iter().map(move |&pattern| Match {
+                pattern,
+                start: index + 1 - self.pattern_lengths[pattern],
+                end: index + 1,
+            })
+        })
+    }
+
+    /// The occurrence starting first (the longest one if several start there), stopping as
+    /// soon as no later occurrence can start before it
+    pub fn find_leftmost(&self, text: impl IntoIterator<Item = u8>) -> Option<Match> {
+        let max_len = self.max_pattern_len();
+        let mut leftmost: Option<Match> = None;
+        for occurrence in self.find_overlapping(text) {
+            if let Some(leftmost) = leftmost {
+                if occurrence.end > leftmost.start + max_len {
+                    break;
+                }
+                let is_better = occurrence.start < leftmost.start
+                    || (occurrence.start == leftmost.start && occurrence.end > leftmost.end);
+                if !is_better {
+                    continue;
+                }
+            }
+            leftmost = Some(occurrence);
+        }
+        leftmost
+    }
+}
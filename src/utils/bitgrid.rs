@@ -0,0 +1,225 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+/// A grid of booleans packed row by row into u64 words (bit x % 64 of word x / 64 of a row
+/// is column x), so that whole rows can be shifted and combined at once
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> bool) -> Self {
+        let mut grid = Self::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if f(x, y) {
+                    grid.set(x, y, true);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Builds a grid from rows of bytes, with the cells equal to the given byte set to true
+    /// (an empty grid if there are no rows)
+    pub fn from_bytes(rows: &[&[u8]], byte: u8) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        Self::from_fn(width, rows.len(), |x, y| rows[y][x] == byte)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.words[y * self.words_per_row + x / 64] & (1 << (x % 64)) != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let word = &mut self.words[y * self.words_per_row + x / 64];
+        if value {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        self.row(y)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width)
+                .filter(move |&x| self.get(x, y))
+                .map(move |x| (x, y))
+        })
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Clears the unused bits beyond the width, at the end of each row
+    fn mask_rows(&mut self) {
+        if !self.width.is_multiple_of(64) {
+            let mask = (1 << (self.width % 64)) - 1;
+            for y in 0..self.height {
+                self.words[(y + 1) * self.words_per_row - 1] &= mask;
+            }
+        }
+    }
+
+    pub fn complement(&self) -> Self {
+        let mut result = self.clone();
+        result.words.iter_mut().for_each(|word| *word = !*word);
+        result.mask_rows();
+        result
+    }
+
+    pub fn and_not(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.and_not_assign(other);
+        result
+    }
+
+    pub fn and_not_assign(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word &= !other);
+    }
+
+    /// Moves every cell one column to the left (towards x = 0), dropping the first column
+    pub fn shift_left(&self) -> Self {
+        let mut result = self.clone();
+        if self.words_per_row == 0 {
+            return result;
+        }
+        for row in result.words.chunks_mut(self.words_per_row) {
+            for i in 0..row.len() {
+                let carry = row.get(i + 1).map_or(0, |next| next << 63);
+                row[i] = (row[i] >> 1) | carry;
+            }
+        }
+        result
+    }
+
+    /// Moves every cell one column to the right, dropping the last column
+    pub fn shift_right(&self) -> Self {
+        let mut result = self.clone();
+        if self.words_per_row == 0 {
+            return result;
+        }
+        for row in result.words.chunks_mut(self.words_per_row) {
+            for i in (0..row.len()).rev() {
+                let carry = if i > 0 { row[i - 1] >> 63 } else { 0 };
+                row[i] = (row[i] << 1) | carry;
+            }
+        }
+        result.mask_rows();
+        result
+    }
+
+    /// Moves every cell one row up (towards y = 0), dropping the first row
+    pub fn shift_up(&self) -> Self {
+        let mut result = Self::new(self.width, self.height);
+        if self.height > 0 {
+            let length = self.words.len() - self.words_per_row;
+            result.words[..length].copy_from_slice(&self.words[self.words_per_row..]);
+        }
+        result
+    }
+
+    /// Moves every cell one row down, dropping the last row
+    pub fn shift_down(&self) -> Self {
+        let mut result = Self::new(self.width, self.height);
+        if self.height > 0 {
+            let length = self.words.len() - self.words_per_row;
+            result.words[self.words_per_row..].copy_from_slice(&self.words[..length]);
+        }
+        result
+    }
+
+    /// Every cell that is adjacent (horizontally or vertically) to a cell of this grid
+    pub fn neighbors(&self) -> Self {
+        let mut result = self.shift_left();
+        result |= &self.shift_right();
+        result |= &self.shift_up();
+        result |= &self.shift_down();
+        result
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word &= other);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word |= other);
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+/// A Graphviz graph in the DOT language, built node by node and edge by edge. Attributes are
+/// (name, value) pairs such as ("shape", "box") or ("label", "x<1416").
+#[derive(Debug, Clone)]
+pub struct Dot {
+    is_directed: bool,
+    statements: Vec<String>,
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn format_attributes(attributes: &[(&str, &str)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let attributes: Vec<_> = attributes
+        .iter()
+        .map(|&(name, value)| format!("{name}={}", quote(value)))
+        .collect();
+    format!(" [{}]", attributes.join(", "))
+}
+
+impl Dot {
+    pub fn directed() -> Self {
+        Self {
+            is_directed: true,
+            statements: vec![],
+        }
+    }
+
+    pub fn undirected() -> Self {
+        Self {
+            is_directed: false,
+            statements: vec![],
+        }
+    }
+
+    /// Sets an attribute of the whole graph, e.g. ("rankdir", "LR")
+    pub fn graph_attribute(&mut self, name: &str, value: &str) {
+        self.statements
+            .push(format!("graph{}", format_attributes(&[(name, value)])));
+    }
+
+    /// Sets the default attributes of every node declared afterwards
+    pub fn node_defaults(&mut self, attributes: &[(&str, &str)]) {
+        self.statements
+            .push(format!("node{}", format_attributes(attributes)));
+    }
+
+    pub fn node(&mut self, id: &str, attributes: &[(&str, &str)]) {
+        self.statements
+            .push(format!("{}{}", quote(id), format_attributes(attributes)));
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attributes: &[(&str, &str)]) {
+        let operator = if self.is_directed { "->" } else { "--" };
+        self.statements.push(format!(
+            "{} {operator} {}{}",
+            quote(from),
+            quote(to),
+            format_attributes(attributes)
+        ));
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keyword = if self.is_directed { "digraph" } else { "graph" };
+        writeln!(f, "{keyword} {{")?;
+        for statement in &self.statements {
+            writeln!(f, "    {statement};")?;
+        }
+        write!(f, "}}")
+    }
+}
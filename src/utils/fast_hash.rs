@@ -0,0 +1,60 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// The multiply-rotate hash used by rustc (FxHash). It is much faster than SipHash on small
+/// keys such as integers and grid positions, but it is not resistant to HashDoS attacks,
+/// which is fine for puzzle inputs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut last = [0; 8];
+            last[..remainder.len()].copy_from_slice(remainder);
+            self.add_to_hash(u64::from_le_bytes(last));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+pub type FastMap<K, V> = hashbrown::HashMap<K, V, FxBuildHasher>;
+pub type FastSet<K> = hashbrown::HashSet<K, FxBuildHasher>;
@@ -0,0 +1,362 @@
+use hashbrown::HashMap;
+use num::Zero;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::Add;
+
+/// A compact graph whose nodes are indices 0..n, each with a name, and whose edges are stored
+/// as adjacency lists in both directions
+#[derive(Debug, Clone, Default)]
+pub struct Graph<W = u32> {
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+    successors: Vec<Vec<(usize, W)>>,
+    predecessors: Vec<Vec<(usize, W)>>,
+    /// For every successor, whether the edge is one direction of an undirected edge
+    is_undirected: Vec<Vec<bool>>,
+}
+
+impl<W: Copy> Graph<W> {
+    pub fn new() -> Self {
+        Self {
+            names: vec![],
+            indices: HashMap::new(),
+            successors: vec![],
+            predecessors: vec![],
+            is_undirected: vec![],
+        }
+    }
+
+    /// Builds a directed graph from (from, to, weight) triples
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str, W)>) -> Self {
+        let mut graph = Self::new();
+        for (from, to, weight) in edges {
+            let (from, to) = (graph.add_node(from), graph.add_node(to));
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    /// Builds an undirected graph (i.e., with edges in both directions) from (a, b, weight) triples
+    pub fn from_undirected_edges<'a>(
+        edges: impl IntoIterator<Item = (&'a str, &'a str, W)>,
+    ) -> Self {
+        let mut graph = Self::new();
+        for (a, b, weight) in edges {
+            let (a, b) = (graph.add_node(a), graph.add_node(b));
+            graph.add_undirected_edge(a, b, weight);
+        }
+        graph
+    }
+
+    /// Returns the index of the node with this name, adding it if needed
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        let index = self.names.len();
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), index);
+        self.successors.push(vec![]);
+        self.predecessors.push(vec![]);
+        self.is_undirected.push(vec![]);
+        index
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        self.push_edge(from, to, weight, false);
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, weight: W) {
+        self.push_edge(a, b, weight, true);
+        self.push_edge(b, a, weight, true);
+    }
+
+    fn push_edge(&mut self, from: usize, to: usize, weight: W, is_undirected: bool) {
+        self.successors[from].push((to, weight));
+        self.predecessors[to].push((from, weight));
+        self.is_undirected[from].push(is_undirected);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    pub fn successors(&self, node: usize) -> &[(usize, W)] {
+        &self.successors[node]
+    }
+
+    pub fn predecessors(&self, node: usize) -> &[(usize, W)] {
+        &self.predecessors[node]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        self.successors
+            .iter()
+            .enumerate()
+            .flat_map(|(from, next)| next.iter().map(move |&(to, weight)| (from, to, weight)))
+    }
+
+    /// Nodes grouped by their distance (in number of edges) from the start node
+    pub fn bfs_layers(&self, start: usize) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.len()];
+        visited[start] = true;
+        let mut layers = vec![vec![start]];
+        loop {
+            let mut next_layer = vec![];
+            for &node in layers.last().unwrap() {
+                for &(next, _) in &self.successors[node] {
+                    if !visited[next] {
+                        visited[next] = true;
+                        next_layer.push(next);
+                    }
+                }
+            }
+            if next_layer.is_empty() {
+                return layers;
+            }
+            layers.push(next_layer);
+        }
+    }
+
+    /// Weakly connected components (edge directions are ignored)
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.len()];
+        let mut components = vec![];
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut component = vec![];
+            let mut to_visit = VecDeque::from([root]);
+            while let Some(node) = to_visit.pop_front() {
+                component.push(node);
+                for &(next, _) in self.successors[node].iter().chain(&self.predecessors[node]) {
+                    if !visited[next] {
+                        visited[next] = true;
+                        to_visit.push_back(next);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Tarjan's algorithm (iterative version). The components are returned in reverse
+    /// topological order.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+        let mut index = vec![UNVISITED; self.len()];
+        let mut lowlink = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+        for root in 0..self.len() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            let mut call_stack = vec![(root, 0)];
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            while let Some(&(node, edge_index)) = call_stack.last() {
+                if let Some(&(next, _)) = self.successors[node].get(edge_index) {
+                    call_stack.last_mut().unwrap().1 += 1;
+                    if index[next] == UNVISITED {
+                        index[next] = next_index;
+                        lowlink[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        call_stack.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlink[node] = lowlink[node].min(index[next]);
+                    }
+                    continue;
+                }
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+                if lowlink[node] == index[node] {
+                    let mut component = vec![];
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Kahn's algorithm, returns None if the graph has a cycle
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut num_incoming: Vec<_> = self.predecessors.iter().map(|p| p.len()).collect();
+        let mut to_visit: VecDeque<_> = (0..self.len())
+            .filter(|&node| num_incoming[node] == 0)
+            .collect();
+        let mut order = vec![];
+        while let Some(node) = to_visit.pop_front() {
+            order.push(node);
+            for &(next, _) in &self.successors[node] {
+                num_incoming[next] -= 1;
+                if num_incoming[next] == 0 {
+                    to_visit.push_back(next);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+}
+
+impl<W: Copy + Ord + Zero + Add<Output = W>> Graph<W> {
+    /// A* search from start to goal, returning the total cost and the path. The heuristic
+    /// must never overestimate the remaining cost.
+    pub fn astar(
+        &self,
+        start: usize,
+        goal: usize,
+        heuristic: impl Fn(usize) -> W,
+    ) -> Option<(W, Vec<usize>)> {
+        let mut costs: Vec<Option<W>> = vec![None; self.len()];
+        let mut previous = vec![usize::MAX; self.len()];
+        let mut to_visit = BinaryHeap::from([Reverse((heuristic(start), W::zero(), start))]);
+        costs[start] = Some(W::zero());
+        while let Some(Reverse((_, cost, node))) = to_visit.pop() {
+            if costs[node].is_some_and(|best| best < cost) {
+                continue; // outdated entry
+            }
+            if node == goal {
+                let mut path = vec![goal];
+                while *path.last().unwrap() != start {
+                    path.push(previous[*path.last().unwrap()]);
+                }
+                path.reverse();
+                return Some((cost, path));
+            }
+            for &(next, weight) in &self.successors[node] {
+                let next_cost = cost + weight;
+                if costs[next].is_none_or(|best| next_cost < best) {
+                    costs[next] = Some(next_cost);
+                    previous[next] = node;
+                    to_visit.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn dijkstra(&self, start: usize, goal: usize) -> Option<(W, Vec<usize>)> {
+        self.astar(start, goal, |_| W::zero())
+    }
+
+    /// Stoer-Wagner algorithm on the undirected version of the graph: returns the weight
+    /// of the minimum cut and the nodes on one side of it. Every edge counts once between its
+    /// two nodes, whether it was added as directed or undirected, so parallel and opposite
+    /// edges add up. Self-loops are ignored.
+    pub fn stoer_wagner(&self) -> (W, Vec<usize>) {
+        let num_vertices = self.len();
+        let mut min_cut = None;
+        let mut best_partition1 = vec![];
+        let mut merged_nodes: Vec<_> = (0..num_vertices).map(|node| vec![node]).collect();
+        let mut edges = vec![vec![W::zero(); num_vertices]; num_vertices];
+        for (from, next) in self.successors.iter().enumerate() {
+            for (&(to, weight), &is_undirected) in next.iter().zip(&self.is_undirected[from]) {
+                if from != to {
+                    edges[from][to] = edges[from][to] + weight;
+                    // the other direction of an undirected edge is in the successors of `to`
+                    if !is_undirected {
+                        edges[to][from] = edges[to][from] + weight;
+                    }
+                }
+            }
+        }
+
+        for _ in 1..num_vertices {
+            let (cut, last_node, previous_node) = min_cut_phase(&merged_nodes, &edges);
+            if min_cut.is_none_or(|min_cut| cut < min_cut) {
+                min_cut = Some(cut);
+                best_partition1 = merged_nodes[last_node].clone();
+            }
+            let mut last = vec![];
+            std::mem::swap(&mut merged_nodes[last_node], &mut last);
+            merged_nodes[previous_node].append(&mut last);
+            for node in 0..num_vertices {
+                if !merged_nodes[node].is_empty() {
+                    edges[node][previous_node] =
+                        edges[node][previous_node] + edges[node][last_node];
+                    edges[previous_node][node] = edges[node][previous_node];
+                }
+            }
+        }
+        (min_cut.unwrap_or(W::zero()), best_partition1)
+    }
+}
+
+fn min_cut_phase<W: Copy + Ord + Zero + Add<Output = W>>(
+    merged_nodes: &[Vec<usize>],
+    edges: &[Vec<W>],
+) -> (W, usize, usize) {
+    let num_vertices = merged_nodes.len();
+    let mut visited = vec![false; num_vertices];
+    let mut num_connections = vec![W::zero(); num_vertices];
+
+    let mut min_cut = W::zero();
+    let mut last_node = 0;
+    let mut previous_node = 0;
+
+    loop {
+        let mut most_connected_node = None;
+        for node in 0..num_vertices {
+            if !merged_nodes[node].is_empty()
+                && !visited[node]
+                && most_connected_node
+                    .is_none_or(|(_, max_connections)| num_connections[node] > max_connections)
+            {
+                most_connected_node = Some((node, num_connections[node]));
+            }
+        }
+        if let Some((most_connected_node, max_connections)) = most_connected_node {
+            previous_node = last_node;
+            last_node = most_connected_node;
+            min_cut = max_connections;
+            visited[most_connected_node] = true;
+            for node in 0..num_vertices {
+                if !merged_nodes[node].is_empty() && !visited[node] {
+                    num_connections[node] =
+                        num_connections[node] + edges[most_connected_node][node];
+                }
+            }
+        } else {
+            return (min_cut, last_node, previous_node);
+        }
+    }
+}
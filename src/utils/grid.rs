@@ -0,0 +1,68 @@
+/// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert!(cells.len() == width * height, "Wrong number of cells");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        &self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self.cells[y * self.width + x] = value;
+    }
+
+    /// Returns the cell at (x, y) if these coordinates are inside the grid
+    pub fn try_get(&self, x: i64, y: i64) -> Option<&T> {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            None
+        } else {
+            Some(self.get(x as usize, y as usize))
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of ASCII characters, one row per line
+    pub fn parse(input: &str) -> Self {
+        let rows: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+        let width = rows.first().map_or(0, |row| row.len());
+        Self::from_fn(width, rows.len(), |x, y| rows[y][x])
+    }
+}
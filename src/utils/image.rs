@@ -0,0 +1,130 @@
+use super::grid::Grid;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+/// An RGB image that can be written as binary PPM or PNG, without any external crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// One pixel per cell, colored by the given closure
+    pub fn from_grid<T>(grid: &Grid<T>, to_color: impl Fn(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.rows().flatten().map(to_color).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Each pixel becomes a square of factor x factor pixels
+    pub fn scaled(&self, factor: usize) -> Self {
+        let grid = Grid::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.get(x / factor, y / factor)
+        });
+        Self::from_grid(&grid, |&color| color)
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut ihdr = vec![];
+        ihdr.extend((self.width as u32).to_be_bytes());
+        ihdr.extend((self.height as u32).to_be_bytes());
+        // bit depth 8, color type RGB, default compression, filter and interlace methods
+        ihdr.extend([8, 2, 0, 0, 0]);
+        // every scanline starts with its filter type, 0 = None
+        let scanlines: Vec<u8> = self
+            .pixels
+            .chunks(self.width.max(1))
+            .flat_map(|row| [0].into_iter().chain(row.iter().flatten().copied()))
+            .collect();
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_png_chunk(&mut bytes, b"IHDR", &ihdr);
+        write_png_chunk(&mut bytes, b"IDAT", &zlib_stored(&scanlines));
+        write_png_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    /// Writes a PPM file if the extension is .ppm, a PNG file otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = if path.extension().is_some_and(|extension| extension == "ppm") {
+            self.to_ppm()
+        } else {
+            self.to_png()
+        };
+        fs::write(path, bytes)
+    }
+}
+
+fn write_png_chunk(bytes: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(chunk_type);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+/// Wraps the data in a zlib stream made of uncompressed ("stored") deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        bytes.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let length = block.len() as u16;
+        bytes.push(is_final as u8);
+        bytes.extend(length.to_le_bytes());
+        bytes.extend((!length).to_le_bytes());
+        bytes.extend(block);
+    }
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
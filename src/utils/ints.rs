@@ -0,0 +1,121 @@
+use num::{Bounded, Zero};
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// What to do with numbers that don't fit in the target type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    Error,
+    Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIntsError {
+    Overflow { position: usize, text: String },
+    WrongCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseIntsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow { position, text } => {
+                write!(f, "number {text} at position {position} is out of range")
+            }
+            Self::WrongCount { expected, found } => {
+                write!(f, "expected {expected} numbers but found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseIntsError {}
+
+/// Iterator over the integers in a string, without any allocation. A '-' is only treated as
+/// a sign if it directly precedes a digit and follows a separator (or the start of the string),
+/// so "5-3" yields 5 and 3, whereas "x=-3" yields -3.
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    input: &'a str,
+    position: usize,
+    signed: bool,
+    overflow: Overflow,
+    item_type: PhantomData<T>,
+}
+
+pub fn ints<T: FromStr>(input: &str, signed: bool) -> Ints<'_, T> {
+    Ints {
+        input,
+        position: 0,
+        signed,
+        overflow: Overflow::Skip,
+        item_type: PhantomData,
+    }
+}
+
+impl<T> Ints<'_, T> {
+    pub fn on_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+impl<T: FromStr> Iterator for Ints<'_, T> {
+    type Item = Result<T, ParseIntsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+        loop {
+            let digits_start =
+                self.position + bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+            let digits_end = digits_start
+                + bytes[digits_start..]
+                    .iter()
+                    .position(|c| !c.is_ascii_digit())
+                    .unwrap_or(bytes.len() - digits_start);
+            self.position = digits_end;
+            let is_negative = self.signed
+                && digits_start > 0
+                && bytes[digits_start - 1] == b'-'
+                && (digits_start == 1 || !bytes[digits_start - 2].is_ascii_alphanumeric());
+            let start = if is_negative {
+                digits_start - 1
+            } else {
+                digits_start
+            };
+            let text = &self.input[start..digits_end];
+            match text.parse() {
+                Ok(number) => return Some(Ok(number)),
+                Err(_) if self.overflow == Overflow::Skip => continue,
+                Err(_) => {
+                    return Some(Err(ParseIntsError::Overflow {
+                        position: start,
+                        text: text.to_string(),
+                    }))
+                }
+            }
+        }
+    }
+}
+
+/// Extracts exactly N integers from the input (signed if T is signed), e.g.,
+/// `parse_n::<i64, 6>("19, 13, 30 @ -2,  1, -2")`
+pub fn parse_n<T, const N: usize>(input: &str) -> Result<[T; N], ParseIntsError>
+where
+    T: FromStr + Bounded + Zero + PartialOrd + Copy,
+{
+    let signed = T::min_value() < T::zero();
+    let mut numbers = [T::zero(); N];
+    let mut found = 0;
+    for number in ints(input, signed).on_overflow(Overflow::Error) {
+        if found < N {
+            numbers[found] = number?;
+        }
+        found += 1;
+    }
+    if found != N {
+        return Err(ParseIntsError::WrongCount { expected: N, found });
+    }
+    Ok(numbers)
+}
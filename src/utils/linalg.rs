@@ -0,0 +1,292 @@
+use num::bigint::BigInt;
+use num::rational::Ratio;
+use num::{Integer, One, ToPrimitive, Zero};
+
+pub type Rational = Ratio<BigInt>;
+
+pub fn rational<T: Into<BigInt>>(n: T) -> Rational {
+    Rational::from_integer(n.into())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    cells: Vec<Vec<Rational>>,
+    num_cols: usize,
+}
+
+/// Result of a Gaussian elimination: the columns that hold a pivot, and the determinant
+/// (only meaningful for square matrices)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elimination {
+    pub pivot_columns: Vec<usize>,
+    pub determinant: Rational,
+}
+
+impl Matrix {
+    pub fn new(cells: Vec<Vec<Rational>>) -> Self {
+        let num_cols = cells.first().map_or(0, |row| row.len());
+        assert!(
+            cells.iter().all(|row| row.len() == num_cols),
+            "All rows must have the same length"
+        );
+        Self { cells, num_cols }
+    }
+
+    pub fn from_ints<T: Into<BigInt> + Copy>(rows: &[Vec<T>]) -> Self {
+        Self::new(
+            rows.iter()
+                .map(|row| row.iter().map(|&n| rational(n)).collect())
+                .collect(),
+        )
+    }
+
+    pub fn identity(size: usize) -> Self {
+        Self::new(
+            (0..size)
+                .map(|i| {
+                    (0..size)
+                        .map(|j| {
+                            if i == j {
+                                Rational::one()
+                            } else {
+                                Rational::zero()
+                            }
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> &Rational {
+        &self.cells[row][col]
+    }
+
+    pub fn rows(&self) -> &[Vec<Rational>] {
+        &self.cells
+    }
+
+    /// Gaussian elimination in place, down to the reduced row echelon form
+    pub fn row_reduce(&mut self) -> Elimination {
+        let mut pivot_columns = vec![];
+        let mut determinant = Rational::one();
+        let mut pivot_row = 0;
+        for col in 0..self.num_cols {
+            if pivot_row == self.num_rows() {
+                break;
+            }
+            let Some(row) =
+                (pivot_row..self.num_rows()).find(|&row| !self.cells[row][col].is_zero())
+            else {
+                determinant = Rational::zero();
+                continue;
+            };
+            if row != pivot_row {
+                self.cells.swap(row, pivot_row);
+                determinant = -determinant;
+            }
+            let pivot = self.cells[pivot_row][col].clone();
+            determinant *= pivot.clone();
+            self.cells[pivot_row]
+                .iter_mut()
+                .for_each(|cell| *cell /= pivot.clone());
+            for row in 0..self.num_rows() {
+                let factor = self.cells[row][col].clone();
+                if row == pivot_row || factor.is_zero() {
+                    continue;
+                }
+                for c in col..self.num_cols {
+                    let delta = factor.clone() * self.cells[pivot_row][c].clone();
+                    self.cells[row][c] -= delta;
+                }
+            }
+            pivot_columns.push(col);
+            pivot_row += 1;
+        }
+        if pivot_row < self.num_rows() {
+            determinant = Rational::zero();
+        }
+        Elimination {
+            pivot_columns,
+            determinant,
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().pivot_columns.len()
+    }
+
+    pub fn determinant(&self) -> Rational {
+        assert!(self.num_rows() == self.num_cols, "Matrix must be square");
+        self.clone().row_reduce().determinant
+    }
+
+    /// Solves self · x = b exactly, returning None unless there is a unique solution
+    pub fn solve(&self, b: &[Rational]) -> Option<Vec<Rational>> {
+        assert!(b.len() == self.num_rows(), "Incompatible dimensions");
+        let mut augmented = Matrix::new(
+            self.cells
+                .iter()
+                .zip(b)
+                .map(|(row, value)| row.iter().chain([value]).cloned().collect())
+                .collect(),
+        );
+        let pivot_columns = augmented.row_reduce().pivot_columns;
+        if pivot_columns.len() != self.num_cols || pivot_columns.contains(&self.num_cols) {
+            return None; // underdetermined or inconsistent
+        }
+        Some(
+            (0..self.num_cols)
+                .map(|row| augmented.cells[row][self.num_cols].clone())
+                .collect(),
+        )
+    }
+}
+
+/// Intersection of the lines p1 + t·d1 and p2 + u·d2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineIntersection {
+    At(Rational, Rational),
+    Parallel,
+    Coincident,
+    Skew,
+}
+
+/// The 2D cross product, or None if it doesn't fit in i128 (or is i128::MIN, which can't be
+/// negated)
+fn checked_cross_2d(a: [i128; 2], b: [i128; 2]) -> Option<i128> {
+    let cross = a[0]
+        .checked_mul(b[1])?
+        .checked_sub(a[1].checked_mul(b[0])?)?;
+    (cross != i128::MIN).then_some(cross)
+}
+
+fn cross_2d(a: [i128; 2], b: [i128; 2]) -> BigInt {
+    BigInt::from(a[0]) * b[1] - BigInt::from(a[1]) * b[0]
+}
+
+/// The fraction numerator / denominator, reduced in i128 (without the gcd of big integers)
+fn small_fraction(numerator: i128, denominator: i128) -> Rational {
+    let divisor = numerator.gcd(&denominator) * denominator.signum();
+    Rational::new_raw(
+        BigInt::from(numerator / divisor),
+        BigInt::from(denominator / divisor),
+    )
+}
+
+/// p2 - p1, which doesn't overflow in i128
+fn difference<const N: usize>(p2: [i64; N], p1: [i64; N]) -> [i128; N] {
+    std::array::from_fn(|i| p2[i] as i128 - p1[i] as i128)
+}
+
+fn cross_3d(a: [BigInt; 3], b: [BigInt; 3]) -> [BigInt; 3] {
+    [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ]
+}
+
+fn dot_3d(a: &[BigInt; 3], b: &[BigInt; 3]) -> BigInt {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+pub fn intersect_lines_2d(
+    p1: [i64; 2],
+    d1: [i64; 2],
+    p2: [i64; 2],
+    d2: [i64; 2],
+) -> LineIntersection {
+    let delta = difference(p2, p1);
+    let (d1, d2) = (d1.map(i128::from), d2.map(i128::from));
+    let crosses = [(d1, d2), (delta, d2), (delta, d1)];
+    // i128 is much faster than big integers, and enough for puzzle-sized numbers
+    if let [Some(denominator), Some(t), Some(u)] = crosses.map(|(a, b)| checked_cross_2d(a, b)) {
+        return match denominator {
+            0 if u == 0 => LineIntersection::Coincident,
+            0 => LineIntersection::Parallel,
+            _ => LineIntersection::At(
+                small_fraction(t, denominator),
+                small_fraction(u, denominator),
+            ),
+        };
+    }
+    let [denominator, t, u] = crosses.map(|(a, b)| cross_2d(a, b));
+    if denominator.is_zero() {
+        if u.is_zero() {
+            LineIntersection::Coincident
+        } else {
+            LineIntersection::Parallel
+        }
+    } else {
+        LineIntersection::At(
+            Rational::new(t, denominator.clone()),
+            Rational::new(u, denominator),
+        )
+    }
+}
+
+pub fn intersect_lines_3d(
+    p1: [i64; 3],
+    d1: [i64; 3],
+    p2: [i64; 3],
+    d2: [i64; 3],
+) -> LineIntersection {
+    let big = |v: [i64; 3]| v.map(BigInt::from);
+    let delta = difference(p2, p1).map(BigInt::from);
+    let normal = cross_3d(big(d1), big(d2));
+    if normal.iter().all(|n| n.is_zero()) {
+        if cross_3d(delta, big(d1)).iter().all(|n| n.is_zero()) {
+            LineIntersection::Coincident
+        } else {
+            LineIntersection::Parallel
+        }
+    } else if !dot_3d(&delta, &normal).is_zero() {
+        LineIntersection::Skew
+    } else {
+        let norm2 = dot_3d(&normal, &normal);
+        LineIntersection::At(
+            Rational::new(
+                dot_3d(&cross_3d(delta.clone(), big(d2)), &normal),
+                norm2.clone(),
+            ),
+            Rational::new(dot_3d(&cross_3d(delta, big(d1)), &normal), norm2),
+        )
+    }
+}
+
+/// Returns the point p + t·d
+pub fn point_at(p: &[i64], d: &[i64], t: &Rational) -> Vec<Rational> {
+    p.iter()
+        .zip(d)
+        .map(|(&p, &d)| rational(p) + t * rational(d))
+        .collect()
+}
+
+/// Whether the point p + t·d is in the box [min, max]ⁿ: the same as checking the coordinates
+/// of point_at, but with i128 instead of rationals when the numbers fit
+pub fn is_point_in_box(p: &[i64], d: &[i64], t: &Rational, min: i64, max: i64) -> bool {
+    if let (Some(numerator), Some(denominator)) = (t.numer().to_i128(), t.denom().to_i128()) {
+        // min ≤ p + d * numerator / denominator ≤ max, with a positive denominator
+        let scaled = |value: i64| (value as i128).checked_mul(denominator);
+        let is_in_box = p.iter().zip(d).try_fold(true, |is_in_box, (&p, &d)| {
+            let coordinate = scaled(p)?.checked_add((d as i128).checked_mul(numerator)?)?;
+            Some(is_in_box && (scaled(min)?..=scaled(max)?).contains(&coordinate))
+        });
+        if let Some(is_in_box) = is_in_box {
+            return is_in_box;
+        }
+    }
+    let area = rational(min)..=rational(max);
+    point_at(p, d, t)
+        .iter()
+        .all(|coordinate| area.contains(coordinate))
+}
@@ -0,0 +1,96 @@
+use hashbrown::HashMap;
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::hash::Hash;
+use std::iter::Sum;
+
+type MemoFn<'f, K, V> = dyn Fn(&Memo<'f, K, V>, K) -> V + 'f;
+
+/// Caches the results of a recursive function, which receives the Memo itself so that it can
+/// make its recursive calls through the cache, e.g.:
+/// `Memo::new(|memo, n: u64| if n < 2 { n } else { memo.get(n - 1) + memo.get(n - 2) })`
+pub struct Memo<'f, K, V> {
+    function: Box<MemoFn<'f, K, V>>,
+    cache: RefCell<HashMap<K, V>>,
+    num_hits: Cell<u64>,
+    num_misses: Cell<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub cache_size: usize,
+    pub num_hits: u64,
+    pub num_misses: u64,
+}
+
+impl MemoStats {
+    pub fn hit_rate(&self) -> f64 {
+        let num_calls = self.num_hits + self.num_misses;
+        if num_calls == 0 {
+            0.0
+        } else {
+            self.num_hits as f64 / num_calls as f64
+        }
+    }
+}
+
+/// The statistics of several caches, added up
+impl Sum for MemoStats {
+    fn sum<I: Iterator<Item = Self>>(stats: I) -> Self {
+        stats.fold(Self::default(), |total, stats| Self {
+            cache_size: total.cache_size + stats.cache_size,
+            num_hits: total.num_hits + stats.num_hits,
+            num_misses: total.num_misses + stats.num_misses,
+        })
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} cached values, {} hits, {} misses ({:.1}% hit rate)",
+            self.cache_size,
+            self.num_hits,
+            self.num_misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+impl<'f, K: Hash + Eq + Clone, V: Clone> Memo<'f, K, V> {
+    pub fn new(function: impl Fn(&Memo<'f, K, V>, K) -> V + 'f) -> Self {
+        Self {
+            function: Box::new(function),
+            cache: RefCell::new(HashMap::new()),
+            num_hits: Cell::new(0),
+            num_misses: Cell::new(0),
+        }
+    }
+
+    pub fn get(&self, key: K) -> V {
+        if let Some(value) = self.cache.borrow().get(&key) {
+            self.num_hits.set(self.num_hits.get() + 1);
+            return value.clone();
+        }
+        self.num_misses.set(self.num_misses.get() + 1);
+        let value = (self.function)(self, key.clone());
+        self.cache.borrow_mut().insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            cache_size: self.cache.borrow().len(),
+            num_hits: self.num_hits.get(),
+            num_misses: self.num_misses.get(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+        self.num_hits.set(0);
+        self.num_misses.set(0);
+    }
+}
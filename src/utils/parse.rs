@@ -0,0 +1,243 @@
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The remaining text to parse, along with its byte position in the original input, and the
+/// furthest error of the parsers that failed so far without failing the whole parse (an
+/// alternative that didn't match, an optional or separated item that wasn't there...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input<'a> {
+    pub text: &'a str,
+    pub position: usize,
+    furthest_error: Option<Rc<ParseError>>,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            position: 0,
+            furthest_error: None,
+        }
+    }
+
+    fn advance(self, length: usize) -> Self {
+        Self {
+            text: &self.text[length..],
+            position: self.position + length,
+            furthest_error: self.furthest_error,
+        }
+    }
+
+    /// Remembers the error of a parser that failed, if it is the furthest one so far
+    fn with_error(mut self, error: ParseError) -> Self {
+        if self
+            .furthest_error
+            .as_ref()
+            .is_none_or(|furthest| error.position > furthest.position)
+        {
+            self.furthest_error = Some(Rc::new(error));
+        }
+        self
+    }
+
+    /// The error at this position, or the furthest one if a parser failed further than that:
+    /// a malformed item that ended a list is a better explanation than what comes after it
+    fn error(self, expected: &str) -> ParseError {
+        match self.furthest_error {
+            Some(furthest) if furthest.position > self.position => Rc::unwrap_or_clone(furthest),
+            _ => ParseError {
+                position: self.position,
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Converts the byte position to 1-based (line, column) numbers in the original input
+    pub fn line_column(&self, input: &str) -> (usize, usize) {
+        let before = &input[..self.position.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |index| index + 1) + 1;
+        (line, column)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {} at position {}",
+            self.expected, self.position
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/// Any function that consumes the start of the input and returns a value plus the rest
+pub trait Parser<'a, T>: Fn(Input<'a>) -> ParseResult<'a, T> {}
+
+impl<'a, T, F: Fn(Input<'a>) -> ParseResult<'a, T>> Parser<'a, T> for F {}
+
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.text.starts_with(expected) {
+            Ok((&input.text[..expected.len()], input.advance(expected.len())))
+        } else {
+            Err(input.error(&format!("{expected:?}")))
+        }
+    }
+}
+
+fn take_while<'a>(input: Input<'a>, predicate: impl Fn(u8) -> bool) -> (&'a str, Input<'a>) {
+    let length = input
+        .text
+        .bytes()
+        .position(|c| !predicate(c))
+        .unwrap_or(input.text.len());
+    (&input.text[..length], input.advance(length))
+}
+
+/// A decimal integer, with an optional leading '-'
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let sign_length = if input.text.starts_with('-') { 1 } else { 0 };
+        let (digits, _) = take_while(input.clone().advance(sign_length), |c| c.is_ascii_digit());
+        let length = sign_length + digits.len();
+        match input.text[..length].parse() {
+            Ok(number) if !digits.is_empty() => Ok((number, input.advance(length))),
+            _ => Err(input.error("an integer")),
+        }
+    }
+}
+
+/// One or more ASCII letters, digits or underscores
+pub fn identifier<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let (name, rest) = take_while(input.clone(), |c| c.is_ascii_alphanumeric() || c == b'_');
+        if name.is_empty() {
+            Err(input.error("an identifier"))
+        } else {
+            Ok((name, rest))
+        }
+    }
+}
+
+/// Zero or more spaces or tabs
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| Ok(take_while(input, |c| c == b' ' || c == b'\t'))
+}
+
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |input| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input| {
+        let (a, rest) = first(input)?;
+        let (b, rest) = second(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Tries the first parser, then the second one if the first one failed
+pub fn alt<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| first(input.clone()).or_else(|error| second(input.with_error(error)))
+}
+
+pub fn optional<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: Input<'a>| match parser(input.clone()) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(error) => Ok((None, input.with_error(error))),
+    }
+}
+
+/// One or more items separated by the separator
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input| {
+        let (first, mut rest) = item(input)?;
+        let mut items = vec![first];
+        loop {
+            let after_separator = match separator(rest.clone()) {
+                Ok((_, after_separator)) => after_separator,
+                Err(error) => return Ok((items, rest.with_error(error))),
+            };
+            match item(after_separator) {
+                Ok((next, after_item)) => {
+                    items.push(next);
+                    rest = after_item;
+                }
+                Err(error) => return Ok((items, rest.with_error(error))),
+            }
+        }
+    }
+}
+
+pub fn key_value<'a, K, S, V>(
+    key: impl Parser<'a, K>,
+    separator: impl Parser<'a, S>,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(terminated(key, separator), value)
+}
+
+/// Splits the input on blank lines, keeping track of the position of each block
+pub fn blocks(input: &str) -> impl Iterator<Item = Input<'_>> {
+    input.split("\n\n").map(move |block| Input {
+        text: block,
+        position: block.as_ptr() as usize - input.as_ptr() as usize,
+        furthest_error: None,
+    })
+}
+
+/// Runs the parser and checks that it consumed the whole input
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, input: Input<'a>) -> Result<T, ParseError> {
+    let (value, rest) = parser(input)?;
+    if rest.text.is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+pub fn parse_str<'a, T>(parser: impl Parser<'a, T>, text: &'a str) -> Result<T, ParseError> {
+    parse_all(parser, Input::new(text))
+}
@@ -0,0 +1,98 @@
+use num::rational::Ratio;
+use num::{Integer, Signed, Zero};
+
+/// Newton forward differences: returns the leading diagonal of the difference table,
+/// i.e., [y₀, Δy₀, Δ²y₀, ...], stopping once a row is entirely zero
+pub fn forward_differences<T: Integer + Signed + Copy>(values: &[T]) -> Vec<T> {
+    let mut row = values.to_vec();
+    let mut differences = vec![];
+    while !row.is_empty() && !row.iter().all(|value| value.is_zero()) {
+        differences.push(row[0]);
+        row = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    }
+    differences
+}
+
+/// Minimum degree of a polynomial that fits the values, or None if there is not enough data
+/// to tell (i.e., no row of the difference table is entirely zero)
+pub fn min_degree<T: Integer + Signed + Copy>(values: &[T]) -> Option<usize> {
+    let mut row = values.to_vec();
+    let mut degree = 0;
+    while !row.is_empty() {
+        if row.iter().all(|value| value.is_zero()) {
+            return Some(degree.max(1) - 1);
+        }
+        row = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        degree += 1;
+    }
+    None
+}
+
+/// Evaluates Newton's forward difference formula at any integer index (possibly negative or
+/// far beyond the data): P(n) = Σ Δᵏy₀ · C(n, k), using only exact integer arithmetic
+pub fn evaluate<T: Integer + Signed + Copy>(differences: &[T], index: T) -> T {
+    let mut total = T::zero();
+    let mut binomial = T::one();
+    let mut k = T::zero();
+    for (i, &difference) in differences.iter().enumerate() {
+        if i > 0 {
+            k = k + T::one();
+            // C(n, k) = C(n, k - 1) · (n - k + 1) / k, and the division is always exact
+            binomial = binomial * (index - k + T::one()) / k;
+        }
+        total = total + difference * binomial;
+    }
+    total
+}
+
+/// Extrapolates the values (sampled at indices 0, 1, 2...) to any other integer index
+pub fn extrapolate<T: Integer + Signed + Copy>(values: &[T], index: T) -> T {
+    evaluate(&forward_differences(values), index)
+}
+
+/// Exact Lagrange interpolation: returns the coefficients (lowest degree first) of the
+/// unique polynomial of minimal degree going through all the points
+pub fn interpolate<T: Integer + Clone>(points: &[(T, T)]) -> Vec<Ratio<T>> {
+    let mut coefficients = vec![Ratio::from_integer(T::zero()); points.len()];
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let mut basis = vec![Ratio::from_integer(T::one())];
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            assert!(xi != xj, "Duplicate x coordinate");
+            // multiply the basis polynomial by (x - xj) / (xi - xj)
+            let denominator = xi.clone() - xj.clone();
+            let mut next = vec![Ratio::from_integer(T::zero()); basis.len() + 1];
+            for (k, coefficient) in basis.iter().enumerate() {
+                next[k + 1] = next[k + 1].clone() + coefficient.clone();
+                next[k] = next[k].clone() - coefficient.clone() * Ratio::from_integer(xj.clone());
+            }
+            basis = next
+                .into_iter()
+                .map(|c| c / Ratio::from_integer(denominator.clone()))
+                .collect();
+        }
+        for (k, coefficient) in basis.into_iter().enumerate() {
+            coefficients[k] =
+                coefficients[k].clone() + coefficient * Ratio::from_integer(yi.clone());
+        }
+    }
+    while coefficients.len() > 1 && coefficients.last().unwrap().is_zero() {
+        coefficients.pop();
+    }
+    coefficients
+}
+
+/// Evaluates a polynomial given its coefficients (lowest degree first), using Horner's method
+pub fn evaluate_polynomial<T: Integer + Clone>(
+    coefficients: &[Ratio<T>],
+    x: &Ratio<T>,
+) -> Ratio<T> {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Ratio::from_integer(T::zero()), |total, coefficient| {
+            total * x.clone() + coefficient.clone()
+        })
+}
@@ -0,0 +1,99 @@
+use super::gcd;
+
+pub type Point = (i64, i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    Degenerate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+/// Follows a list of (direction, number of steps) moves starting from the origin, and
+/// returns the vertex reached after each move (the last one is the origin if the path is closed)
+pub fn trace(moves: impl IntoIterator<Item = (Point, i64)>) -> Vec<Point> {
+    moves
+        .into_iter()
+        .scan((0, 0), |(x, y), ((dx, dy), num_steps)| {
+            *x += dx * num_steps;
+            *y += dy * num_steps;
+            Some((*x, *y))
+        })
+        .collect()
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+fn cross(a: Point, b: Point, p: Point) -> i64 {
+    (b.0 - a.0) * (p.1 - a.1) - (p.0 - a.0) * (b.1 - a.1)
+}
+
+/// Shoelace formula: twice the signed area, positive if the vertices are counterclockwise
+/// (with the y axis pointing up), so the result is always an exact integer
+pub fn double_signed_area(vertices: &[Point]) -> i64 {
+    edges(vertices).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum()
+}
+
+pub fn orientation(vertices: &[Point]) -> Orientation {
+    match double_signed_area(vertices).signum() {
+        1 => Orientation::CounterClockwise,
+        -1 => Orientation::Clockwise,
+        _ => Orientation::Degenerate,
+    }
+}
+
+/// Number of lattice points on the edges of the polygon
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd((b.0 - a.0).abs(), (b.1 - a.1).abs()))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, using Pick's theorem:
+/// area = interior + boundary / 2 - 1
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_signed_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+/// Number of lattice points inside or on the polygon (i.e., the number of grid cells it covers)
+pub fn lattice_points(vertices: &[Point]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Locates a point relative to the polygon using the winding number, in exact arithmetic
+pub fn locate(vertices: &[Point], point: Point) -> Location {
+    let mut winding_number = 0;
+    for (a, b) in edges(vertices) {
+        let side = cross(a, b, point);
+        if side == 0
+            && (a.0.min(b.0)..=a.0.max(b.0)).contains(&point.0)
+            && (a.1.min(b.1)..=a.1.max(b.1)).contains(&point.1)
+        {
+            return Location::OnBoundary;
+        }
+        if a.1 <= point.1 {
+            if b.1 > point.1 && side > 0 {
+                winding_number += 1;
+            }
+        } else if b.1 <= point.1 && side < 0 {
+            winding_number -= 1;
+        }
+    }
+    if winding_number == 0 {
+        Location::Outside
+    } else {
+        Location::Inside
+    }
+}
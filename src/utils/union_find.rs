@@ -0,0 +1,170 @@
+/// Disjoint sets over the elements 0..n, with path compression and union by rank
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    num_components: usize,
+}
+
+impl UnionFind {
+    pub fn new(num_elements: usize) -> Self {
+        Self {
+            parents: (0..num_elements).collect(),
+            ranks: vec![0; num_elements],
+            sizes: vec![1; num_elements],
+            num_components: num_elements,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut element = element;
+        while self.parents[element] != root {
+            let parent = self.parents[element];
+            self.parents[element] = root;
+            element = parent;
+        }
+        root
+    }
+
+    /// Merges the components of a and b, returns false if they were already merged
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.ranks[a] < self.ranks[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        if self.ranks[a] == self.ranks[b] {
+            self.ranks[a] += 1;
+        }
+        self.num_components -= 1;
+        true
+    }
+
+    pub fn is_connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    /// All the components, each sorted, in the order of their smallest element
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut component_index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = vec![];
+        for element in 0..self.len() {
+            let root = self.find(element);
+            if component_index[root] == usize::MAX {
+                component_index[root] = components.len();
+                components.push(vec![]);
+            }
+            components[component_index[root]].push(element);
+        }
+        components
+    }
+}
+
+/// Union-find without path compression, so that unions can be undone in reverse order.
+/// This is handy for offline "what if we remove this edge" queries: add every other edge,
+/// take a snapshot, try something, then roll back to the snapshot.
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    history: Vec<Option<(usize, usize)>>,
+    num_components: usize,
+}
+
+impl RollbackUnionFind {
+    pub fn new(num_elements: usize) -> Self {
+        Self {
+            parents: (0..num_elements).collect(),
+            sizes: vec![1; num_elements],
+            history: vec![],
+            num_components: num_elements,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn find(&self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            element = self.parents[element];
+        }
+        element
+    }
+
+    /// Merges the components of a and b (union by size), returns false if they were already
+    /// merged. Either way, the operation is recorded so it can be rolled back.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            self.history.push(None);
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.num_components -= 1;
+        self.history.push(Some((a, b)));
+        true
+    }
+
+    pub fn is_connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&self, element: usize) -> usize {
+        self.sizes[self.find(element)]
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    /// Number of recorded unions, to pass to rollback() later
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes every union performed since the snapshot was taken
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            if let Some((root, child)) = self.history.pop().unwrap() {
+                self.parents[child] = child;
+                self.sizes[root] -= self.sizes[child];
+                self.num_components += 1;
+            }
+        }
+    }
+}
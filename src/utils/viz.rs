@@ -0,0 +1,187 @@
+use super::fast_hash::FastSet;
+use super::grid::Grid;
+
+use std::io::Write;
+use std::thread::sleep;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The ANSI SGR parameters for this color, as a foreground or background color
+    fn ansi_code(self, is_background: bool) -> String {
+        let offset = if is_background { 10 } else { 0 };
+        match self {
+            Color::Black => format!("{}", 30 + offset),
+            Color::Red => format!("{}", 31 + offset),
+            Color::Green => format!("{}", 32 + offset),
+            Color::Yellow => format!("{}", 33 + offset),
+            Color::Blue => format!("{}", 34 + offset),
+            Color::Magenta => format!("{}", 35 + offset),
+            Color::Cyan => format!("{}", 36 + offset),
+            Color::White => format!("{}", 37 + offset),
+            Color::Gray => format!("{}", 90 + offset),
+            Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", 38 + offset),
+        }
+    }
+}
+
+/// How to draw the cells of an overlay: any field left to None keeps the underlying look
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub symbol: Option<char>,
+}
+
+impl Style {
+    pub fn foreground(color: Color) -> Self {
+        Self {
+            foreground: Some(color),
+            ..Self::default()
+        }
+    }
+
+    pub fn background(color: Color) -> Self {
+        Self {
+            background: Some(color),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_symbol(self, symbol: char) -> Self {
+        Self {
+            symbol: Some(symbol),
+            ..self
+        }
+    }
+
+    /// Applies the other style on top of this one
+    fn merge(self, other: Style) -> Self {
+        Self {
+            foreground: other.foreground.or(self.foreground),
+            background: other.background.or(self.background),
+            symbol: other.symbol.or(self.symbol),
+        }
+    }
+}
+
+struct Overlay {
+    cells: FastSet<(usize, usize)>,
+    style: Style,
+}
+
+/// A grid plus overlays (paths, visited cells, highlighted points...), drawn in the order
+/// they were added, so later overlays take precedence
+pub struct Scene<'a, T> {
+    grid: &'a Grid<T>,
+    to_char: Box<dyn Fn(&T) -> char + 'a>,
+    overlays: Vec<Overlay>,
+}
+
+impl<'a, T> Scene<'a, T> {
+    pub fn new(grid: &'a Grid<T>, to_char: impl Fn(&T) -> char + 'a) -> Self {
+        Self {
+            grid,
+            to_char: Box::new(to_char),
+            overlays: vec![],
+        }
+    }
+
+    pub fn overlay(
+        mut self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        style: Style,
+    ) -> Self {
+        self.overlays.push(Overlay {
+            cells: cells.into_iter().collect(),
+            style,
+        });
+        self
+    }
+
+    /// Renders the scene as text with ANSI color codes
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let style = self
+                    .overlays
+                    .iter()
+                    .filter(|overlay| overlay.cells.contains(&(x, y)))
+                    .fold(Style::default(), |style, overlay| {
+                        style.merge(overlay.style)
+                    });
+                let symbol = style
+                    .symbol
+                    .unwrap_or_else(|| (self.to_char)(self.grid.get(x, y)));
+                let codes: Vec<_> = style
+                    .foreground
+                    .map(|color| color.ansi_code(false))
+                    .into_iter()
+                    .chain(style.background.map(|color| color.ansi_code(true)))
+                    .collect();
+                if codes.is_empty() {
+                    output.push(symbol);
+                } else {
+                    output += &format!("\x1b[{}m{symbol}\x1b[0m", codes.join(";"));
+                }
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// A sequence of rendered frames that can be replayed in the terminal
+#[derive(Debug, Clone, Default)]
+pub struct Animation {
+    frames: Vec<String>,
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record<T>(&mut self, scene: &Scene<T>) {
+        self.frames.push(scene.render());
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// Clears the terminal and draws every frame in turn, at the given speed
+    pub fn play(&self, frames_per_second: f64) {
+        let delay = Duration::from_secs_f64(1.0 / frames_per_second);
+        let mut stdout = std::io::stdout();
+        for (index, frame) in self.frames.iter().enumerate() {
+            print!("\x1b[H\x1b[2J{frame}");
+            stdout.flush().unwrap();
+            if index + 1 < self.frames.len() {
+                sleep(delay);
+            }
+        }
+    }
+}